@@ -0,0 +1,56 @@
+//! Audible alert for errors and connection drops, played through the OS's own sound tools.
+
+#[cfg(not(target_os = "windows"))]
+use super::util::hidden_command;
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Minimum gap between two alerts, so a burst of errors plays once.
+const DEBOUNCE: Duration = Duration::from_secs(10);
+
+#[derive(Default)]
+pub struct AlertSound {
+    last_played: Option<Instant>,
+}
+
+impl AlertSound {
+    /// Play the alert at `volume` (0.0–1.0) unless one played within the debounce window.
+    pub fn trigger(&mut self, volume: f32) {
+        if self.last_played.is_some_and(|at| at.elapsed() < DEBOUNCE) {
+            return;
+        }
+        self.last_played = Some(Instant::now());
+        play(volume);
+    }
+}
+
+/// Play the platform's error sound on a background thread. Volume is honoured on macOS
+/// (`afplay`) and Linux (`paplay`); Windows plays the system error sound at the system
+/// volume. Nothing plays where the player is missing.
+pub fn play(volume: f32) {
+    let volume = volume.clamp(0.0, 1.0);
+    thread::spawn(move || {
+        #[cfg(target_os = "windows")]
+        {
+            use windows_sys::Win32::UI::WindowsAndMessaging::{MessageBeep, MB_ICONHAND};
+            // Played asynchronously by the system, so nothing cuts it short. It has no volume
+            // of its own.
+            let _ = volume;
+            unsafe {
+                MessageBeep(MB_ICONHAND);
+            }
+        }
+        #[cfg(target_os = "macos")]
+        let _ = hidden_command("afplay")
+            .args(["-v", &format!("{volume:.2}")])
+            .arg("/System/Library/Sounds/Basso.aiff")
+            .status();
+        #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+        let _ = hidden_command("paplay")
+            .arg(format!("--volume={}", (volume * 65536.0) as u32))
+            .arg("/usr/share/sounds/freedesktop/stereo/dialog-error.oga")
+            .status();
+    });
+}
@@ -0,0 +1,244 @@
+//! Minimal ANSI SGR handling for terminal output: foreground colors, bold and reset. The
+//! log keeps the raw escapes; they are interpreted only when the text is displayed.
+
+use eframe::egui::Color32;
+use std::borrow::Cow;
+use std::ops::Range;
+
+/// A styled stretch of the stripped text.
+pub struct Span {
+    pub range: Range<usize>, // byte range in the stripped text
+    pub color: Option<Color32>,
+    pub bold: bool,
+}
+
+#[derive(Clone, Copy, Default, PartialEq)]
+struct Style {
+    color: Option<Color32>,
+    bold: bool,
+}
+
+/// Remove escape sequences, returning the text unchanged when it has none.
+pub fn strip(text: &str) -> Cow<'_, str> {
+    if text.contains('\x1b') {
+        Cow::Owned(parse(text).0)
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// Split `text` into the visible text and the styled spans within it. Non-SGR escape
+/// sequences are dropped; unknown SGR parameters are ignored.
+pub fn parse(text: &str) -> (String, Vec<Span>) {
+    let mut out = String::with_capacity(text.len());
+    let mut spans = Vec::new();
+    let mut style = Style::default();
+    let mut span_start = 0;
+    let mut rest = text;
+
+    while let Some(esc) = rest.find('\x1b') {
+        out.push_str(&rest[..esc]);
+        rest = &rest[esc + 1..];
+        let Some(body) = rest.strip_prefix('[') else {
+            continue; // a lone ESC: drop it
+        };
+        // A CSI sequence runs up to its final byte (@ through ~).
+        let Some(end) = body.find(|c: char| ('@'..='~').contains(&c)) else {
+            rest = "";
+            break;
+        };
+        let (params, final_byte) = (&body[..end], &body[end..end + 1]);
+        rest = &body[end + 1..];
+        if final_byte != "m" {
+            continue;
+        }
+        let next = apply_sgr(style, params);
+        if next != style {
+            push_span(&mut spans, span_start..out.len(), style);
+            span_start = out.len();
+            style = next;
+        }
+    }
+    out.push_str(rest);
+    push_span(&mut spans, span_start..out.len(), style);
+    (out, spans)
+}
+
+fn push_span(spans: &mut Vec<Span>, range: Range<usize>, style: Style) {
+    if !range.is_empty() && style != Style::default() {
+        spans.push(Span {
+            range,
+            color: style.color,
+            bold: style.bold,
+        });
+    }
+}
+
+fn apply_sgr(mut style: Style, params: &str) -> Style {
+    if params.is_empty() {
+        return Style::default(); // `ESC[m` is a reset
+    }
+    // An empty parameter means 0; one that isn't a number up to 255 is skipped.
+    let mut codes = params.split(';').map(|p| {
+        if p.is_empty() {
+            Some(0)
+        } else {
+            p.parse::<u8>().ok()
+        }
+    });
+    while let Some(code) = codes.next() {
+        let Some(code) = code else {
+            continue;
+        };
+        match code {
+            0 => style = Style::default(),
+            1 => style.bold = true,
+            22 => style.bold = false,
+            30..=37 => style.color = Some(basic_color(code - 30, false)),
+            90..=97 => style.color = Some(basic_color(code - 90, true)),
+            39 => style.color = None,
+            38 => match codes.next().flatten() {
+                Some(5) => {
+                    if let Some(index) = codes.next().flatten() {
+                        style.color = Some(indexed_color(index));
+                    }
+                }
+                Some(2) => {
+                    let (r, g, b) = (codes.next(), codes.next(), codes.next());
+                    if let (Some(Some(r)), Some(Some(g)), Some(Some(b))) = (r, g, b) {
+                        style.color = Some(Color32::from_rgb(r, g, b));
+                    }
+                }
+                _ => {}
+            },
+            48 => {
+                // Backgrounds aren't drawn, but their arguments must still be skipped.
+                match codes.next().flatten() {
+                    Some(5) => {
+                        codes.next();
+                    }
+                    Some(2) => {
+                        codes.nth(2);
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+    style
+}
+
+/// The eight standard colors, or their bright variants.
+fn basic_color(index: u8, bright: bool) -> Color32 {
+    let (normal, light) = match index {
+        0 => ((0, 0, 0), (128, 128, 128)),
+        1 => ((205, 49, 49), (241, 76, 76)),
+        2 => ((13, 188, 121), (35, 209, 139)),
+        3 => ((229, 229, 16), (245, 245, 67)),
+        4 => ((36, 114, 200), (59, 142, 234)),
+        5 => ((188, 63, 188), (214, 112, 214)),
+        6 => ((17, 168, 205), (41, 184, 219)),
+        _ => ((229, 229, 229), (255, 255, 255)),
+    };
+    let (r, g, b) = if bright { light } else { normal };
+    Color32::from_rgb(r, g, b)
+}
+
+/// 256-color palette: the 16 basic colors, a 6×6×6 cube, then a gray ramp.
+fn indexed_color(index: u8) -> Color32 {
+    match index {
+        0..=7 => basic_color(index, false),
+        8..=15 => basic_color(index - 8, true),
+        16..=231 => {
+            let i = index - 16;
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            Color32::from_rgb(level(i / 36), level(i / 6 % 6), level(i % 6))
+        }
+        _ => {
+            let gray = 8 + (index - 232) * 10;
+            Color32::from_rgb(gray, gray, gray)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn styles(text: &str) -> (String, Vec<(Range<usize>, Option<Color32>, bool)>) {
+        let (out, spans) = parse(text);
+        let spans = spans
+            .into_iter()
+            .map(|s| (s.range, s.color, s.bold))
+            .collect();
+        (out, spans)
+    }
+
+    #[test]
+    fn several_color_segments() {
+        let (out, spans) = styles("\x1b[31mred\x1b[0m plain \x1b[32mgreen\x1b[39m end");
+        assert_eq!(out, "red plain green end");
+        assert_eq!(
+            spans,
+            vec![
+                (0..3, Some(basic_color(1, false)), false),
+                (10..15, Some(basic_color(2, false)), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_sgr_resets() {
+        let (out, spans) = styles("\x1b[1;93mwarn\x1b[m ok");
+        assert_eq!(out, "warn ok");
+        assert_eq!(spans, vec![(0..4, Some(basic_color(3, true)), true)]);
+    }
+
+    #[test]
+    fn bold_on_and_off() {
+        let (out, spans) = styles("\x1b[1mbold\x1b[22m normal");
+        assert_eq!(out, "bold normal");
+        assert_eq!(spans, vec![(0..4, None, true)]);
+    }
+
+    #[test]
+    fn extended_colors() {
+        let (_, spans) = styles("\x1b[38;5;196ma\x1b[38;2;10;20;30mb\x1b[0m");
+        assert_eq!(
+            spans,
+            vec![
+                (0..1, Some(indexed_color(196)), false),
+                (1..2, Some(Color32::from_rgb(10, 20, 30)), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn background_arguments_are_skipped() {
+        // 5 and 1 are arguments of 48; the 1 must not turn on bold.
+        let (_, spans) = styles("\x1b[48;5;1;32mx");
+        assert_eq!(spans, vec![(0..1, Some(basic_color(2, false)), false)]);
+    }
+
+    #[test]
+    fn non_sgr_csi_is_dropped() {
+        let (out, spans) = styles("\x1b[2K\x1b[1Gprogress 50%");
+        assert_eq!(out, "progress 50%");
+        assert!(spans.is_empty());
+        assert_eq!(strip("no escapes"), "no escapes");
+    }
+
+    #[test]
+    fn invalid_parameter_is_skipped_not_a_reset() {
+        let (out, spans) = styles("\x1b[31mred\x1b[999;300;1m still red");
+        assert_eq!(out, "red still red");
+        assert_eq!(
+            spans,
+            vec![
+                (0..3, Some(basic_color(1, false)), false),
+                (3..13, Some(basic_color(1, false)), true),
+            ]
+        );
+    }
+}
@@ -0,0 +1,51 @@
+//! Timestamped copies of the ThetaTerminal config file, kept next to it.
+
+use chrono::Local;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Copy `path` to `<name>.<timestamp>.bak` in the same directory, then delete the oldest
+/// backups of that file so at most `keep` remain. Returns the new backup's path.
+pub fn backup_file(path: &Path, keep: usize) -> std::io::Result<PathBuf> {
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "no file name"))?;
+    let stamp = Local::now().format("%Y%m%d-%H%M%S");
+    let backup = path.with_file_name(format!("{name}.{stamp}.bak"));
+    fs::copy(path, &backup)?;
+    prune(path, keep.max(1));
+    Ok(backup)
+}
+
+/// Existing backups of `path`, oldest first (the timestamp sorts lexically).
+pub fn list_backups(path: &Path) -> Vec<PathBuf> {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return Vec::new();
+    };
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let prefix = format!("{name}.");
+    let mut backups: Vec<PathBuf> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            p.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|n| n.starts_with(&prefix) && n.ends_with(".bak"))
+        })
+        .collect();
+    backups.sort();
+    backups
+}
+
+fn prune(path: &Path, keep: usize) {
+    let backups = list_backups(path);
+    let excess = backups.len().saturating_sub(keep);
+    for old in &backups[..excess] {
+        let _ = fs::remove_file(old);
+    }
+}
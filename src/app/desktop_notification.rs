@@ -0,0 +1,16 @@
+//! Desktop notifications, for events worth knowing about while another app has focus.
+
+use std::thread;
+
+/// Show a notification on a background thread, since some platforms block until it is
+/// delivered. Failures are ignored; whatever raised it is in the log as well.
+pub fn show(summary: &str, body: &str) {
+    let (summary, body) = (summary.to_string(), body.to_string());
+    thread::spawn(move || {
+        let _ = notify_rust::Notification::new()
+            .appname("ThetaData Terminal GUI")
+            .summary(&summary)
+            .body(&body)
+            .show();
+    });
+}
@@ -0,0 +1,54 @@
+//! `--start-detached`: start the terminal with the saved settings so that it outlives this
+//! process, wait until it reports a live connection, print the outcome and exit. No window
+//! is opened.
+
+use super::status::StatusReport;
+use super::ThetaApp;
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+const TICK: Duration = Duration::from_millis(100);
+/// How long to wait for the terminal to report a connection before giving up on it.
+const READY_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// Exit codes: 0 ready, 1 failed to start or exited early, 2 still not ready at the
+/// timeout (the terminal is left running).
+pub fn run() -> i32 {
+    let mut app = ThetaApp::new();
+    app.headless = true;
+    app.detached = true;
+    print!("{}", app.log_buffer.take());
+
+    app.start_terminal();
+    let Some(pid) = app.process.as_ref().map(|child| child.id()) else {
+        // start_terminal already logged why (missing jar, credentials, …).
+        println!("Terminal did not start.");
+        return 1;
+    };
+
+    let deadline = Instant::now() + READY_TIMEOUT;
+    loop {
+        if let Some(status) = app.supervise() {
+            println!("Terminal exited before it was ready ({status}).");
+            return 1;
+        }
+        if app
+            .terminal_status
+            .as_ref()
+            .is_some_and(StatusReport::is_connected)
+        {
+            println!("Terminal is ready (PID {pid}).");
+            return 0;
+        }
+        if Instant::now() >= deadline {
+            println!(
+                "Terminal is still not ready after {}s; leaving it running (PID {pid}).",
+                READY_TIMEOUT.as_secs()
+            );
+            return 2;
+        }
+        thread::sleep(TICK);
+    }
+}
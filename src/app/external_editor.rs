@@ -0,0 +1,33 @@
+//! Opens the config file in an editor outside the app.
+
+use std::{io, path::Path, process::Command};
+
+/// Launch `editor_command` (program plus any arguments, split on whitespace) with `path`
+/// appended, or the OS default handler for the file when the command is empty.
+pub fn open_in_editor(path: &Path, editor_command: &str) -> io::Result<()> {
+    let mut parts = editor_command.split_whitespace();
+    let mut command = match parts.next() {
+        Some(program) => {
+            let mut command = Command::new(program);
+            command.args(parts);
+            command
+        }
+        None => default_handler(),
+    };
+    command.arg(path).spawn().map(|_| ())
+}
+
+#[cfg(target_os = "windows")]
+fn default_handler() -> Command {
+    Command::new("explorer")
+}
+
+#[cfg(target_os = "macos")]
+fn default_handler() -> Command {
+    Command::new("open")
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+fn default_handler() -> Command {
+    Command::new("xdg-open")
+}
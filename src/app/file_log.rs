@@ -0,0 +1,110 @@
+//! Writes the log to one file per day alongside the app config.
+
+use chrono::{Local, NaiveDate};
+use std::{
+    fs::{self, File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// Prefix for the continuation rows of a hard-wrapped line.
+const WRAP_MARKER: &str = "↪ ";
+
+pub struct FileLog {
+    dir: PathBuf,
+    date: NaiveDate,
+    file: File,
+    pending: String, // text after the last newline, written once the line completes
+}
+
+impl FileLog {
+    pub fn open(dir: PathBuf) -> std::io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        let date = Local::now().date_naive();
+        let file = Self::open_for(&dir, date)?;
+        Ok(Self {
+            dir,
+            date,
+            file,
+            pending: String::new(),
+        })
+    }
+
+    pub fn path_for(dir: &Path, date: NaiveDate) -> PathBuf {
+        dir.join(format!("thetadata_{}.log", date.format("%Y-%m-%d")))
+    }
+
+    /// Path of the file currently being written.
+    pub fn current_path(&self) -> PathBuf {
+        Self::path_for(&self.dir, self.date)
+    }
+
+    fn open_for(dir: &Path, date: NaiveDate) -> std::io::Result<File> {
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(Self::path_for(dir, date))
+    }
+
+    /// Empty the file currently being written and start it with a marker line. The screen
+    /// buffer is unaffected; a partial line not yet written is kept.
+    pub fn truncate(&mut self) -> std::io::Result<PathBuf> {
+        let path = self.current_path();
+        truncate_with_marker(&path)?;
+        // Reopen so the append handle's position follows the new, shorter file.
+        self.file = Self::open_for(&self.dir, self.date)?;
+        Ok(path)
+    }
+
+    /// Append text, writing only complete lines. With `wrap_width`, lines longer than that many
+    /// characters are split across rows prefixed with a continuation marker.
+    pub fn write(&mut self, text: &str, wrap_width: Option<usize>) -> std::io::Result<()> {
+        self.pending.push_str(text);
+        while let Some(pos) = self.pending.find('\n') {
+            let line: String = self.pending.drain(..=pos).collect();
+            let line = line.trim_end_matches(['\r', '\n']);
+
+            let today = Local::now().date_naive();
+            if today != self.date {
+                self.file = Self::open_for(&self.dir, today)?;
+                self.date = today;
+            }
+
+            match wrap_width {
+                Some(width) => {
+                    for row in wrap_line(line, width) {
+                        writeln!(self.file, "{row}")?;
+                    }
+                }
+                None => writeln!(self.file, "{line}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Truncate `path` to zero length and write a `--- truncated at <time> ---` marker.
+pub fn truncate_with_marker(path: &Path) -> std::io::Result<()> {
+    let mut file = OpenOptions::new().write(true).truncate(true).open(path)?;
+    writeln!(
+        file,
+        "--- truncated at {} ---",
+        Local::now().format("%Y-%m-%d %H:%M:%S")
+    )
+}
+
+/// Hard-wrap `line` at `width` characters; continuation rows start with `WRAP_MARKER`.
+pub fn wrap_line(line: &str, width: usize) -> Vec<String> {
+    let marker_len = WRAP_MARKER.chars().count();
+    let width = width.max(marker_len + 1);
+    let chars: Vec<char> = line.chars().collect();
+    if chars.len() <= width {
+        return vec![line.to_string()];
+    }
+
+    let mut rows = vec![chars[..width].iter().collect::<String>()];
+    for chunk in chars[width..].chunks(width - marker_len) {
+        rows.push(format!("{WRAP_MARKER}{}", chunk.iter().collect::<String>()));
+    }
+    rows
+}
@@ -0,0 +1,203 @@
+//! Find and replace for the config editor, which egui's `TextEdit` doesn't offer.
+//!
+//! Matching is per line, so a pattern never spans a line break, and comment lines can be
+//! left out entirely.
+
+use regex::{NoExpand, Regex, RegexBuilder};
+use std::ops::Range;
+
+/// State of the Config tab's replace bar. Session-only.
+#[derive(Default)]
+pub struct FindReplace {
+    pub find: String,
+    pub replace: String,
+    pub case_insensitive: bool,
+    pub regex: bool,
+    /// Skip `#`/`!` comment lines.
+    pub code_only: bool,
+    /// Index of the match last selected with "Find next", in `ranges` order.
+    pub current: Option<usize>,
+    /// Byte range for the editor to select and scroll to on its next draw.
+    pub select: Option<Range<usize>>,
+}
+
+impl FindReplace {
+    /// The search as a regex; plain searches are escaped. `None` while the find box is empty.
+    pub fn matcher(&self) -> Option<Result<Regex, String>> {
+        if self.find.is_empty() {
+            return None;
+        }
+        let pattern = if self.regex {
+            self.find.clone()
+        } else {
+            regex::escape(&self.find)
+        };
+        Some(
+            RegexBuilder::new(&pattern)
+                .case_insensitive(self.case_insensitive)
+                .build()
+                .map_err(|e| e.to_string()),
+        )
+    }
+
+    /// Byte ranges of every match in `text`, in order.
+    pub fn ranges(&self, text: &str, re: &Regex) -> Vec<Range<usize>> {
+        let mut ranges = Vec::new();
+        let mut line_start = 0;
+        for line in text.split_inclusive('\n') {
+            if !self.skips(line) {
+                let body = line.trim_end_matches(['\r', '\n']);
+                ranges.extend(
+                    re.find_iter(body)
+                        .map(|m| line_start + m.start()..line_start + m.end()),
+                );
+            }
+            line_start += line.len();
+        }
+        ranges
+    }
+
+    /// Replace match number `index`, as numbered by `ranges`, and return the new text;
+    /// `None` if there is no such match.
+    pub fn replace_at(&self, text: &str, re: &Regex, index: usize) -> Option<String> {
+        let range = self.ranges(text, re).into_iter().nth(index)?;
+        let mut replacement = String::new();
+        if self.regex {
+            let line_start = text[..range.start].rfind('\n').map_or(0, |i| i + 1);
+            let line_end = text[range.start..]
+                .find('\n')
+                .map_or(text.len(), |i| range.start + i);
+            let body = text[line_start..line_end].trim_end_matches('\r');
+            re.captures_at(body, range.start - line_start)?
+                .expand(&self.replace, &mut replacement);
+        } else {
+            replacement.push_str(&self.replace);
+        }
+        Some(format!(
+            "{}{replacement}{}",
+            &text[..range.start],
+            &text[range.end..]
+        ))
+    }
+
+    /// Replace every match and return the new text and how many were replaced. Regex
+    /// searches expand `$1`-style groups; plain ones insert the text as is.
+    pub fn replace_all(&self, text: &str, re: &Regex) -> (String, usize) {
+        let mut out = String::with_capacity(text.len());
+        let mut replaced = 0;
+        for line in text.split_inclusive('\n') {
+            if self.skips(line) {
+                out.push_str(line);
+                continue;
+            }
+            // Keep the line ending out of reach of patterns like `\s+$`.
+            let body = line.trim_end_matches(['\r', '\n']);
+            let new_body = if self.regex {
+                re.replace_all(body, self.replace.as_str())
+            } else {
+                re.replace_all(body, NoExpand(&self.replace))
+            };
+            out.push_str(&new_body);
+            out.push_str(&line[body.len()..]);
+            replaced += re.find_iter(body).count();
+        }
+        (out, replaced)
+    }
+
+    fn skips(&self, line: &str) -> bool {
+        let trimmed = line.trim_start();
+        self.code_only && (trimmed.starts_with('#') || trimmed.starts_with('!'))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn search(find: &str, replace: &str) -> FindReplace {
+        FindReplace {
+            find: find.to_string(),
+            replace: replace.to_string(),
+            ..FindReplace::default()
+        }
+    }
+
+    fn matcher(fr: &FindReplace) -> Regex {
+        fr.matcher().unwrap().unwrap()
+    }
+
+    #[test]
+    fn regex_mode_expands_groups() {
+        let fr = FindReplace {
+            regex: true,
+            ..search(r"PORT=(\d+)", "PORT=${1}0")
+        };
+        let re = matcher(&fr);
+        let text = "HTTP_PORT=25510\nWS_PORT=25520\n";
+        assert_eq!(
+            fr.replace_all(text, &re),
+            ("HTTP_PORT=255100\nWS_PORT=255200\n".to_string(), 2)
+        );
+        assert_eq!(
+            fr.replace_at(text, &re, 1).as_deref(),
+            Some("HTTP_PORT=25510\nWS_PORT=255200\n")
+        );
+        assert_eq!(fr.replace_at(text, &re, 2), None);
+    }
+
+    #[test]
+    fn plain_mode_inserts_replacement_as_is() {
+        let fr = search("(25510)", "$1");
+        let re = matcher(&fr);
+        assert_eq!(
+            fr.replace_all("HTTP_PORT=(25510)\n", &re),
+            ("HTTP_PORT=$1\n".to_string(), 1)
+        );
+    }
+
+    #[test]
+    fn case_insensitive_mode() {
+        let text = "HTTP_PORT=1\nhttp_port=2\n";
+        let fr = search("http_port", "REST_PORT");
+        assert_eq!(
+            fr.replace_all(text, &matcher(&fr)),
+            ("HTTP_PORT=1\nREST_PORT=2\n".to_string(), 1)
+        );
+
+        let fr = FindReplace {
+            case_insensitive: true,
+            ..fr
+        };
+        let re = matcher(&fr);
+        assert_eq!(
+            fr.replace_all(text, &re),
+            ("REST_PORT=1\nREST_PORT=2\n".to_string(), 2)
+        );
+        assert_eq!(
+            fr.replace_at(text, &re, 0).as_deref(),
+            Some("REST_PORT=1\nhttp_port=2\n")
+        );
+    }
+
+    #[test]
+    fn code_only_skips_comment_lines() {
+        let text = "#HTTP_PORT=1\n  ! HTTP_PORT=2\nHTTP_PORT=3\n";
+        let fr = FindReplace {
+            code_only: true,
+            ..search("HTTP_PORT", "REST_PORT")
+        };
+        let re = matcher(&fr);
+        assert_eq!(fr.ranges(text, &re).len(), 1);
+        assert_eq!(
+            fr.replace_all(text, &re),
+            (
+                "#HTTP_PORT=1\n  ! HTTP_PORT=2\nREST_PORT=3\n".to_string(),
+                1
+            )
+        );
+        assert_eq!(
+            fr.replace_at(text, &re, 0).as_deref(),
+            Some("#HTTP_PORT=1\n  ! HTTP_PORT=2\nREST_PORT=3\n")
+        );
+    }
+}
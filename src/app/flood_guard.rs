@@ -0,0 +1,69 @@
+//! Keeps the UI responsive when the terminal floods its output, by showing only a sample
+//! of lines while the rate stays above a threshold.
+
+use std::time::{Duration, Instant};
+
+/// How long the rate must stay above the threshold before sampling starts.
+const SUSTAINED_SECS: u32 = 2;
+
+pub struct FloodGuard {
+    window_start: Instant,
+    window_lines: usize,
+    hot_secs: u32,               // consecutive one-second windows over the threshold
+    sample_every: Option<usize>, // show one line in this many while sampling
+    counter: usize,
+    pub last_rate: usize, // lines per second in the last full window
+}
+
+impl Default for FloodGuard {
+    fn default() -> Self {
+        Self {
+            window_start: Instant::now(),
+            window_lines: 0,
+            hot_secs: 0,
+            sample_every: None,
+            counter: 0,
+            last_rate: 0,
+        }
+    }
+}
+
+impl FloodGuard {
+    /// Count a batch of incoming lines and, once a second, decide whether to sample.
+    pub fn record(&mut self, lines: usize, threshold: usize) {
+        self.window_lines += lines;
+        let elapsed = self.window_start.elapsed();
+        if elapsed < Duration::from_secs(1) {
+            return;
+        }
+        self.last_rate = (self.window_lines as f64 / elapsed.as_secs_f64()) as usize;
+        self.window_start = Instant::now();
+        self.window_lines = 0;
+
+        if self.last_rate > threshold {
+            self.hot_secs += 1;
+            if self.hot_secs >= SUSTAINED_SECS {
+                // Aim to display about a tenth of the threshold per second.
+                let target = (threshold / 10).max(1);
+                self.sample_every = Some((self.last_rate / target).max(2));
+            }
+        } else {
+            self.hot_secs = 0;
+            self.sample_every = None;
+        }
+    }
+
+    /// Whether the next line should be displayed.
+    pub fn should_show(&mut self) -> bool {
+        let Some(every) = self.sample_every else {
+            return true;
+        };
+        self.counter = (self.counter + 1) % every;
+        self.counter == 0
+    }
+
+    /// `Some(n)` while only every n-th line is shown.
+    pub fn sampling(&self) -> Option<usize> {
+        self.sample_every
+    }
+}
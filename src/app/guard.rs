@@ -0,0 +1,29 @@
+//! `--guard <pid>`: a helper process that stops the terminal once the GUI that started it is
+//! gone, however it went. The GUI holds the write end of the helper's stdin; the OS closes
+//! it when the GUI exits or crashes, which ends the wait below.
+
+use super::ports;
+use std::{
+    io,
+    process::{Child, Command, Stdio},
+};
+
+/// Start a guard for the terminal with `pid`. The returned child's stdin has to stay open
+/// for as long as the terminal should live; kill the guard first on a normal shutdown.
+pub fn spawn(pid: u32) -> io::Result<Child> {
+    Command::new(std::env::current_exe()?)
+        .args(["--guard", &pid.to_string()])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+}
+
+/// Block until stdin closes, then kill `pid`. Exit code 0 if the kill succeeded.
+pub fn run(pid: u32) -> i32 {
+    let _ = io::copy(&mut io::stdin(), &mut io::sink());
+    match ports::kill_process(pid) {
+        Ok(true) => 0,
+        _ => 1,
+    }
+}
@@ -0,0 +1,616 @@
+use super::redact::Redactor;
+use super::severity;
+use super::util::hidden_command;
+use crate::model::JvmProfile;
+use std::{
+    io::{BufRead, BufReader, ErrorKind, Read},
+    path::Path,
+    process::Stdio,
+    sync::mpsc::{channel, RecvTimeoutError, Sender},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// Run `<java> -version` and return the first line it prints, e.g.
+/// `openjdk version "21.0.2"`. `java` is a path or a program name looked up on PATH.
+pub fn check_java_available(java: &str) -> Result<String, String> {
+    let mut command = hidden_command(java);
+    let output = command
+        .arg("-version")
+        .output()
+        .map_err(|e| spawn_error(java, e))?;
+
+    // `java -version` reports on stderr.
+    String::from_utf8_lossy(&output.stderr)
+        .lines()
+        .next()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .ok_or_else(|| "java -version printed nothing".to_string())
+}
+
+fn spawn_error(java: &str, e: std::io::Error) -> String {
+    match e.kind() {
+        ErrorKind::NotFound if java == "java" => "Java not found on PATH".to_string(),
+        ErrorKind::NotFound => format!("Java not found at {java}"),
+        _ => format!("Failed to run {java}: {e}"),
+    }
+}
+
+/// How long "Test jar" waits for `--version` before giving up.
+const JAR_TEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Run `java -jar <jar> --version` on its own, without credentials, and return the line that
+/// reports the version (or the first line printed). Blocks for up to `JAR_TEST_TIMEOUT`.
+pub fn test_jar(java: &str, jar_path: &str) -> Result<String, String> {
+    if !Path::new(jar_path).is_file() {
+        return Err(format!("Jar not found: {jar_path}"));
+    }
+    let mut command = hidden_command(java);
+    let mut child = command
+        .arg("-jar")
+        .arg(jar_path)
+        .arg("--version")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| spawn_error(java, e))?;
+
+    let started = Instant::now();
+    let timed_out = loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break false,
+            Ok(None) if started.elapsed() < JAR_TEST_TIMEOUT => {
+                thread::sleep(Duration::from_millis(100))
+            }
+            _ => {
+                let _ = child.kill();
+                break true;
+            }
+        }
+    };
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Failed to read output: {e}"))?;
+
+    let text = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let lines: Vec<&str> = text
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty())
+        .collect();
+    // A class-version error means the jar is fine but Java is too old; say so directly.
+    if let Some((required, running)) = lines.iter().find_map(|l| parse_class_version_error(l)) {
+        let running = running.map_or_else(|| "older".to_string(), |v| v.to_string());
+        return Err(format!("Needs Java {required}, found {running}"));
+    }
+    let reported = lines
+        .iter()
+        .find(|l| l.to_lowercase().contains("version"))
+        .or(lines.first());
+    match (reported, timed_out) {
+        (Some(line), _) => Ok(line.to_string()),
+        (None, true) => Err("No output within 10 s".to_string()),
+        (None, false) if output.status.success() => Err("The jar printed nothing".to_string()),
+        (None, false) => Err(format!("The jar exited with {}", output.status)),
+    }
+}
+
+/// How long "Test Login" waits for the terminal to report the login result.
+const LOGIN_TEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Run the jar with the given credentials until it reports that it connected or that the
+/// login was rejected, then kill it. Blocks for up to `LOGIN_TEST_TIMEOUT`. The returned
+/// text never contains the credentials.
+pub fn test_login(
+    java: &str,
+    jar_path: &str,
+    jvm_args: &[String],
+    username: &str,
+    password: &str,
+) -> Result<String, String> {
+    if !Path::new(jar_path).is_file() {
+        return Err(format!("Jar not found: {jar_path}"));
+    }
+    let mut command = hidden_command(java);
+    let mut child = command
+        .args(jvm_args)
+        .arg("-jar")
+        .arg(jar_path)
+        .arg(username)
+        .arg(password)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| spawn_error(java, e))?;
+
+    let (tx, rx) = channel();
+    if let Some(stdout) = child.stdout.take() {
+        forward_lines(stdout, tx.clone());
+    }
+    if let Some(stderr) = child.stderr.take() {
+        forward_lines(stderr, tx);
+    }
+
+    let redactor = Redactor::new([username, password]);
+    let started = Instant::now();
+    let mut last_line = None;
+    let result = loop {
+        let remaining = LOGIN_TEST_TIMEOUT.saturating_sub(started.elapsed());
+        let mut line = match rx.recv_timeout(remaining) {
+            Ok(line) => line,
+            Err(RecvTimeoutError::Timeout) => {
+                break Err("No login result within 30 s".to_string());
+            }
+            // Both streams closed: the terminal exited without saying either way.
+            Err(RecvTimeoutError::Disconnected) => {
+                break Err(last_line.map_or_else(
+                    || "The terminal exited before logging in".to_string(),
+                    |line| format!("The terminal exited: {line}"),
+                ));
+            }
+        };
+        redactor.redact(&mut line);
+        let line = line.trim().to_string();
+        if severity::is_auth_failure(&line) {
+            break Err(line);
+        }
+        let upper = line.to_ascii_uppercase();
+        if upper.contains("CONNECTED") && !upper.contains("DISCONNECTED") {
+            break Ok(line);
+        }
+        if !line.is_empty() {
+            last_line = Some(line);
+        }
+    };
+    let _ = child.kill();
+    let _ = child.wait();
+    result
+}
+
+/// Send each line read from `stream` to `tx` on a background thread.
+fn forward_lines(stream: impl Read + Send + 'static, tx: Sender<String>) {
+    thread::spawn(move || {
+        for line in BufReader::new(stream).lines().flatten() {
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+/// Major version from a `java -version` line: `"1.8.0_292"` → 8, `"21.0.2"` → 21.
+pub fn parse_java_major(version_line: &str) -> Option<u32> {
+    let quoted = version_line.split('"').nth(1)?;
+    let mut parts = quoted.split(|c: char| !c.is_ascii_digit());
+    let first: u32 = parts.next()?.parse().ok()?;
+    if first == 1 {
+        parts.next()?.parse().ok()
+    } else {
+        Some(first)
+    }
+}
+
+/// Parse an `UnsupportedClassVersionError` line into the required and (if reported) running
+/// Java major versions.
+pub fn parse_class_version_error(line: &str) -> Option<(u32, Option<u32>)> {
+    if !line.contains("UnsupportedClassVersionError") {
+        return None;
+    }
+    // Java 9+ says "class file version 65.0"; Java 8 and older "major.minor version 65.0".
+    let required = number_after(line, "class file version ")
+        .or_else(|| number_after(line, "major.minor version "))?;
+    let running = number_after(line, "up to ");
+    Some((
+        java_major_for_class_version(required),
+        running.map(java_major_for_class_version),
+    ))
+}
+
+/// Class file versions are offset by 44 from the Java release (52 = Java 8, 65 = Java 21).
+fn java_major_for_class_version(class_version: u32) -> u32 {
+    class_version.saturating_sub(44)
+}
+
+fn number_after(line: &str, marker: &str) -> Option<u32> {
+    let start = line.find(marker)? + marker.len();
+    let digits: String = line[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    digits.parse().ok()
+}
+
+/// The OS timezone as a Java zone ID: `TZ`, then the `/etc/localtime` link or
+/// `/etc/timezone` on Unix, or the Windows zone from `tzutil` mapped to its IANA name.
+/// `None` if none of these gives a zone; a fixed offset would get daylight saving wrong.
+pub fn system_timezone() -> Option<String> {
+    std::env::var("TZ")
+        .ok()
+        .map(|tz| tz.trim_start_matches(':').to_string())
+        .filter(|tz| !tz.is_empty())
+        .or_else(os_timezone)
+}
+
+#[cfg(unix)]
+fn os_timezone() -> Option<String> {
+    let linked = || {
+        let target = std::fs::read_link("/etc/localtime").ok()?;
+        let target = target.to_string_lossy();
+        let (_, zone) = target.split_once("zoneinfo/")?;
+        Some(zone.to_string())
+    };
+    linked().or_else(|| {
+        let zone = std::fs::read_to_string("/etc/timezone").ok()?;
+        Some(zone.trim().to_string()).filter(|zone| !zone.is_empty())
+    })
+}
+
+#[cfg(target_os = "windows")]
+fn os_timezone() -> Option<String> {
+    let output = hidden_command("tzutil").arg("/g").output().ok()?;
+    let name = String::from_utf8_lossy(&output.stdout);
+    windows_zone_to_iana(name.trim()).map(str::to_string)
+}
+
+#[cfg(not(any(unix, target_os = "windows")))]
+fn os_timezone() -> Option<String> {
+    None
+}
+
+/// Windows zone names and the IANA zone CLDR maps each to for the world ("001") territory.
+/// A zone with daylight saving turned off (`…_dstoff`) has no IANA equivalent.
+#[cfg(any(target_os = "windows", test))]
+const WINDOWS_ZONES: &[(&str, &str)] = &[
+    ("Dateline Standard Time", "Etc/GMT+12"),
+    ("UTC-11", "Etc/GMT+11"),
+    ("Hawaiian Standard Time", "Pacific/Honolulu"),
+    ("Alaskan Standard Time", "America/Anchorage"),
+    ("Pacific Standard Time (Mexico)", "America/Tijuana"),
+    ("Pacific Standard Time", "America/Los_Angeles"),
+    ("US Mountain Standard Time", "America/Phoenix"),
+    ("Mountain Standard Time", "America/Denver"),
+    ("Central America Standard Time", "America/Guatemala"),
+    ("Central Standard Time (Mexico)", "America/Mexico_City"),
+    ("Central Standard Time", "America/Chicago"),
+    ("Canada Central Standard Time", "America/Regina"),
+    ("SA Pacific Standard Time", "America/Bogota"),
+    ("Eastern Standard Time (Mexico)", "America/Cancun"),
+    ("Eastern Standard Time", "America/New_York"),
+    ("US Eastern Standard Time", "America/Indiana/Indianapolis"),
+    ("Venezuela Standard Time", "America/Caracas"),
+    ("Atlantic Standard Time", "America/Halifax"),
+    ("SA Western Standard Time", "America/La_Paz"),
+    ("Pacific SA Standard Time", "America/Santiago"),
+    ("Newfoundland Standard Time", "America/St_Johns"),
+    ("E. South America Standard Time", "America/Sao_Paulo"),
+    ("Argentina Standard Time", "America/Argentina/Buenos_Aires"),
+    ("SA Eastern Standard Time", "America/Cayenne"),
+    ("UTC", "Etc/UTC"),
+    ("GMT Standard Time", "Europe/London"),
+    ("Greenwich Standard Time", "Atlantic/Reykjavik"),
+    ("W. Europe Standard Time", "Europe/Berlin"),
+    ("Central Europe Standard Time", "Europe/Budapest"),
+    ("Romance Standard Time", "Europe/Paris"),
+    ("Central European Standard Time", "Europe/Warsaw"),
+    ("W. Central Africa Standard Time", "Africa/Lagos"),
+    ("GTB Standard Time", "Europe/Bucharest"),
+    ("FLE Standard Time", "Europe/Kiev"),
+    ("E. Europe Standard Time", "Europe/Chisinau"),
+    ("Israel Standard Time", "Asia/Jerusalem"),
+    ("South Africa Standard Time", "Africa/Johannesburg"),
+    ("Egypt Standard Time", "Africa/Cairo"),
+    ("Turkey Standard Time", "Europe/Istanbul"),
+    ("Russian Standard Time", "Europe/Moscow"),
+    ("Arab Standard Time", "Asia/Riyadh"),
+    ("Arabian Standard Time", "Asia/Dubai"),
+    ("Iran Standard Time", "Asia/Tehran"),
+    ("Pakistan Standard Time", "Asia/Karachi"),
+    ("India Standard Time", "Asia/Kolkata"),
+    ("Nepal Standard Time", "Asia/Kathmandu"),
+    ("Bangladesh Standard Time", "Asia/Dhaka"),
+    ("SE Asia Standard Time", "Asia/Bangkok"),
+    ("China Standard Time", "Asia/Shanghai"),
+    ("Singapore Standard Time", "Asia/Singapore"),
+    ("Taipei Standard Time", "Asia/Taipei"),
+    ("W. Australia Standard Time", "Australia/Perth"),
+    ("Tokyo Standard Time", "Asia/Tokyo"),
+    ("Korea Standard Time", "Asia/Seoul"),
+    ("Cen. Australia Standard Time", "Australia/Adelaide"),
+    ("AUS Central Standard Time", "Australia/Darwin"),
+    ("E. Australia Standard Time", "Australia/Brisbane"),
+    ("AUS Eastern Standard Time", "Australia/Sydney"),
+    ("Tasmania Standard Time", "Australia/Hobart"),
+    ("New Zealand Standard Time", "Pacific/Auckland"),
+];
+
+/// The IANA zone for a Windows zone name such as `Eastern Standard Time`.
+#[cfg(any(target_os = "windows", test))]
+fn windows_zone_to_iana(name: &str) -> Option<&'static str> {
+    WINDOWS_ZONES
+        .iter()
+        .find(|(windows, _)| *windows == name)
+        .map(|(_, iana)| *iana)
+}
+
+pub const JVM_PROFILES: [JvmProfile; 4] = [
+    JvmProfile::Default,
+    JvmProfile::LowLatency,
+    JvmProfile::LowMemory,
+    JvmProfile::Custom,
+];
+
+pub fn jvm_profile_label(profile: JvmProfile) -> &'static str {
+    match profile {
+        JvmProfile::Default => "Default",
+        JvmProfile::LowLatency => "Low-latency (ZGC)",
+        JvmProfile::LowMemory => "Low-memory",
+        JvmProfile::Custom => "Custom",
+    }
+}
+
+/// One-line explanation shown under the profile picker.
+pub fn jvm_profile_description(profile: JvmProfile) -> &'static str {
+    match profile {
+        JvmProfile::Default => "No extra flags; the JVM picks its own collector and heap size.",
+        JvmProfile::LowLatency => {
+            "ZGC keeps GC pauses under a millisecond, at the cost of some extra CPU and memory. \
+             Good for streaming under heavy load."
+        }
+        JvmProfile::LowMemory => {
+            "Serial GC with a 512 MB heap cap that returns free memory to the OS. \
+             Good for small machines; pauses are longer under load."
+        }
+        JvmProfile::Custom => "Uses the JVM arguments entered below.",
+    }
+}
+
+/// Flags for `profile`; `custom_args` is only used by `JvmProfile::Custom`.
+pub fn jvm_profile_flags(profile: JvmProfile, custom_args: &str) -> Vec<String> {
+    let flags: &[&str] = match profile {
+        JvmProfile::Default => &[],
+        JvmProfile::LowLatency => &["-XX:+UseZGC"],
+        JvmProfile::LowMemory => &[
+            "-XX:+UseSerialGC",
+            "-Xmx512m",
+            "-XX:MinHeapFreeRatio=10",
+            "-XX:MaxHeapFreeRatio=20",
+        ],
+        JvmProfile::Custom => return split_jvm_args(custom_args),
+    };
+    flags.iter().map(|flag| flag.to_string()).collect()
+}
+
+/// Split a JVM argument string on whitespace, keeping `"…"` and `'…'` segments together
+/// with the quotes removed: `-Dapp.name="Theta Data" -Xmx2g` → `-Dapp.name=Theta Data`,
+/// `-Xmx2g`. Blank input gives no arguments.
+pub fn split_jvm_args(args: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+    for c in args.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => current.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            None if c.is_whitespace() => {
+                if in_arg {
+                    parts.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            None => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+    if in_arg {
+        parts.push(current);
+    }
+    parts
+}
+
+/// Config keys for the terminal's own ports, which it also reads as `-D` system properties.
+const TERMINAL_PORT_PROPERTIES: &[&str] = &["HTTP_PORT", "WS_PORT"];
+
+/// Problems with extra JVM arguments that clash with what the app passes itself: the jar
+/// and the credentials after it, and the port, which comes from the config file. Also
+/// flags the same option given twice, where only one of the values takes effect.
+pub fn conflicting_jvm_args(args: &[String], username: &str, password: &str) -> Vec<String> {
+    let mut conflicts = Vec::new();
+    let mut seen: Vec<&str> = Vec::new();
+    for arg in args {
+        if matches!(arg.as_str(), "-jar" | "-cp" | "-classpath" | "--class-path") {
+            conflicts.push(format!("{arg}: the jar is launched by the app"));
+        } else if !username.is_empty() && arg == username {
+            conflicts.push("the username is passed by the app".to_string());
+        } else if !password.is_empty() && arg == password {
+            // Don't echo the password back.
+            conflicts.push("the password is passed by the app".to_string());
+        } else if is_terminal_port_property(arg) {
+            conflicts.push(format!("{arg}: the port is set in the config file"));
+        }
+
+        let option = option_name(arg);
+        if seen.contains(&option) {
+            conflicts.push(format!("{option} is given more than once"));
+        } else {
+            seen.push(option);
+        }
+    }
+    conflicts
+}
+
+/// Whether `arg` sets one of the terminal's own ports (`-DHTTP_PORT=…`), which would fight
+/// the config file. Other ports such as `-Dhttp.proxyPort` are none of the app's business.
+fn is_terminal_port_property(arg: &str) -> bool {
+    arg.contains('=')
+        && option_name(arg)
+            .strip_prefix("-D")
+            .is_some_and(|name| TERMINAL_PORT_PROPERTIES.contains(&name))
+}
+
+/// The part of a JVM option that identifies it: `-Xmx2g` → `-Xmx`, `-Dkey=v` → `-Dkey`,
+/// `-XX:+UseZGC` and `-XX:-UseZGC` → `UseZGC`.
+fn option_name(arg: &str) -> &str {
+    for prefix in ["-Xmx", "-Xms", "-Xss"] {
+        if arg.starts_with(prefix) {
+            return prefix;
+        }
+    }
+    let name = arg.split('=').next().unwrap_or(arg);
+    name.strip_prefix("-XX:+")
+        .or_else(|| name.strip_prefix("-XX:-"))
+        .unwrap_or(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn class_version_error_from_a_newer_runtime() {
+        let line = "Exception in thread \"main\" java.lang.UnsupportedClassVersionError: \
+                    net/thetadata/terminal/Main has been compiled by a more recent version of \
+                    the Java Runtime (class file version 65.0), this version of the Java \
+                    Runtime only recognizes class file versions up to 52.0";
+        assert_eq!(parse_class_version_error(line), Some((21, Some(8))));
+    }
+
+    #[test]
+    fn class_version_error_from_java_7() {
+        let line = "Exception in thread \"main\" java.lang.UnsupportedClassVersionError: \
+                    net/thetadata/terminal/Main : Unsupported major.minor version 55.0";
+        assert_eq!(parse_class_version_error(line), Some((11, None)));
+    }
+
+    #[test]
+    fn other_lines_are_not_class_version_errors() {
+        assert_eq!(parse_class_version_error("class file version 65.0"), None);
+        assert_eq!(parse_class_version_error("[INFO] Starting terminal"), None);
+    }
+
+    #[test]
+    fn windows_zones_map_to_iana() {
+        assert_eq!(
+            windows_zone_to_iana("Eastern Standard Time"),
+            Some("America/New_York")
+        );
+        assert_eq!(
+            windows_zone_to_iana("Eastern Standard Time (Mexico)"),
+            Some("America/Cancun")
+        );
+        assert_eq!(windows_zone_to_iana("Eastern Standard Time_dstoff"), None);
+        assert_eq!(windows_zone_to_iana(""), None);
+    }
+
+    #[test]
+    fn split_keeps_quoted_segments_together() {
+        assert_eq!(
+            split_jvm_args(r#"-Dapp.name="Theta Data" -Xmx2g '-Dpath=/a b/c'"#),
+            ["-Dapp.name=Theta Data", "-Xmx2g", "-Dpath=/a b/c"]
+        );
+    }
+
+    #[test]
+    fn split_blank_input_gives_no_arguments() {
+        assert!(split_jvm_args("").is_empty());
+        assert!(split_jvm_args(" \t\n  ").is_empty());
+    }
+
+    #[test]
+    fn split_keeps_empty_quoted_argument() {
+        assert_eq!(split_jvm_args(r#"-a "" -b"#), ["-a", "", "-b"]);
+        assert_eq!(split_jvm_args("''"), [""]);
+    }
+
+    #[test]
+    fn jar_and_classpath_flags_conflict() {
+        for flag in ["-jar", "-cp", "-classpath", "--class-path"] {
+            let conflicts = conflicting_jvm_args(&args(&[flag]), "", "");
+            assert_eq!(
+                conflicts,
+                [format!("{flag}: the jar is launched by the app")]
+            );
+        }
+    }
+
+    #[test]
+    fn credential_echoes_conflict_without_showing_the_password() {
+        let conflicts = conflicting_jvm_args(
+            &args(&["me@example.com", "s3cret"]),
+            "me@example.com",
+            "s3cret",
+        );
+        assert_eq!(
+            conflicts,
+            [
+                "the username is passed by the app",
+                "the password is passed by the app"
+            ]
+        );
+        assert!(conflicts.iter().all(|c| !c.contains("s3cret")));
+    }
+
+    #[test]
+    fn empty_credentials_match_nothing() {
+        assert!(conflicting_jvm_args(&args(&["-Xmx2g"]), "", "").is_empty());
+    }
+
+    #[test]
+    fn terminal_port_properties_conflict() {
+        let conflicts =
+            conflicting_jvm_args(&args(&["-DHTTP_PORT=25511", "-DWS_PORT=25521"]), "", "");
+        assert_eq!(
+            conflicts,
+            [
+                "-DHTTP_PORT=25511: the port is set in the config file",
+                "-DWS_PORT=25521: the port is set in the config file"
+            ]
+        );
+    }
+
+    #[test]
+    fn other_port_properties_do_not_conflict() {
+        let other = args(&[
+            "-Dhttp.proxyPort=8080",
+            "-Dhttps.proxyPort=8443",
+            "-Dcom.sun.management.jmxremote.port=9010",
+        ]);
+        assert!(conflicting_jvm_args(&other, "", "").is_empty());
+    }
+
+    #[test]
+    fn duplicated_option_conflicts() {
+        let conflicts = conflicting_jvm_args(&args(&["-Xmx2g", "-Xmx4g"]), "", "");
+        assert_eq!(conflicts, ["-Xmx is given more than once"]);
+
+        let conflicts = conflicting_jvm_args(
+            &args(&["-XX:+UseZGC", "-XX:-UseZGC", "-Dk=1", "-Dk=2"]),
+            "",
+            "",
+        );
+        assert_eq!(
+            conflicts,
+            [
+                "UseZGC is given more than once",
+                "-Dk is given more than once"
+            ]
+        );
+    }
+}
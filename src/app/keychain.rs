@@ -0,0 +1,30 @@
+//! Check that the macOS keychain is reachable, run on request or after a failed save.
+//! Packaged builds missing the keychain entitlement fail every `keyring` call, which
+//! otherwise looks like bad credentials.
+
+use keyring::Entry;
+
+/// Account name of the throwaway entry used for the round-trip.
+const SELF_TEST_ACCOUNT: &str = "keychain-self-test";
+
+/// Write, read back and delete a dummy entry under the app's keychain service.
+pub fn round_trip(service: &str) -> Result<(), String> {
+    let entry = Entry::new(service, SELF_TEST_ACCOUNT);
+    let probe = "ok";
+
+    entry
+        .set_password(probe)
+        .map_err(|e| format!("write failed: {e}"))?;
+    let read = entry
+        .get_password()
+        .map_err(|e| format!("read failed: {e}"));
+    // Clean up even when the read failed.
+    let deleted = entry
+        .delete_password()
+        .map_err(|e| format!("delete failed: {e}"));
+
+    match read? {
+        value if value == probe => deleted,
+        _ => Err("read back a different value".to_string()),
+    }
+}
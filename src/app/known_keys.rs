@@ -0,0 +1,58 @@
+//! Config keys ThetaTerminal is known to read. Anything else in the config is most likely a
+//! typo, which the terminal ignores without a word. Add new options here as ThetaData ships
+//! them.
+
+use super::properties::ConfigEntry;
+
+/// Keys matched exactly.
+const KNOWN_KEYS: &[&str] = &[
+    "MDDS_REGION",
+    "FPSS_REGION",
+    "HTTP_PORT",
+    "WS_PORT",
+    "HTTP_CONCURRENCY",
+];
+
+/// Per-region host lists, e.g. `MDDS_NJ_HOSTS`; any region name is accepted.
+const KNOWN_PATTERNS: &[(&str, &str)] = &[("MDDS_", "_HOSTS"), ("FPSS_", "_HOSTS")];
+
+pub fn is_known(key: &str) -> bool {
+    KNOWN_KEYS.contains(&key)
+        || KNOWN_PATTERNS.iter().any(|(prefix, suffix)| {
+            key.len() > prefix.len() + suffix.len()
+                && key.starts_with(prefix)
+                && key.ends_with(suffix)
+        })
+}
+
+/// Entries whose key isn't known, in file order.
+pub fn unknown_keys(entries: &[ConfigEntry]) -> Vec<&ConfigEntry> {
+    entries.iter().filter(|e| !is_known(&e.key)).collect()
+}
+
+/// The known key `key` is most likely a misspelling of: at most two edits away, ignoring
+/// case.
+pub fn suggestion(key: &str) -> Option<&'static str> {
+    let key = key.to_ascii_uppercase();
+    KNOWN_KEYS
+        .iter()
+        .map(|known| (edit_distance(&key, known), *known))
+        .filter(|(distance, _)| *distance <= 2)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, known)| known)
+}
+
+/// Levenshtein distance over chars.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
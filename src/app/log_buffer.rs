@@ -0,0 +1,154 @@
+//! The on-screen log, kept as whole lines so the oldest can be dropped cheaply.
+
+use std::collections::VecDeque;
+
+/// Log text split into complete lines plus the unfinished last one. Offsets handed out by
+/// `end_offset` count every byte ever pushed, so they stay valid as lines are trimmed.
+#[derive(Default)]
+pub struct LogBuffer {
+    lines: VecDeque<String>, // each ends with '\n'
+    partial: String,
+    len: usize,           // bytes currently held, `partial` included
+    dropped_bytes: usize, // bytes trimmed off the front so far
+}
+
+impl LogBuffer {
+    /// Append text, then drop the oldest complete lines beyond `max_lines`.
+    pub fn push_str(&mut self, text: &str, max_lines: usize) {
+        self.len += text.len();
+        let mut rest = text;
+        while let Some(pos) = rest.find('\n') {
+            let mut line = std::mem::take(&mut self.partial);
+            line.push_str(&rest[..=pos]);
+            self.lines.push_back(line);
+            rest = &rest[pos + 1..];
+        }
+        self.partial.push_str(rest);
+
+        while self.lines.len() > max_lines.max(1) {
+            if let Some(old) = self.lines.pop_front() {
+                self.len -= old.len();
+                self.dropped_bytes += old.len();
+            }
+        }
+    }
+
+    /// The retained text as one string.
+    pub fn text(&self) -> String {
+        let mut text = String::with_capacity(self.len);
+        for line in &self.lines {
+            text.push_str(line);
+        }
+        text.push_str(&self.partial);
+        text
+    }
+
+    /// Lines held, the unfinished last one included.
+    pub fn line_count(&self) -> usize {
+        self.lines.len() + usize::from(!self.partial.is_empty())
+    }
+
+    /// Line `index` (0 is the oldest held) without its line ending.
+    pub fn line(&self, index: usize) -> Option<&str> {
+        let line = match self.lines.get(index) {
+            Some(line) => line,
+            None if index == self.lines.len() && !self.partial.is_empty() => &self.partial,
+            None => return None,
+        };
+        Some(line.trim_end_matches(['\r', '\n']))
+    }
+
+    /// Index of the line starting at an `end_offset` value, or `None` once it was trimmed.
+    pub fn line_index(&self, offset: usize) -> Option<usize> {
+        let mut local = self.local_offset(offset)?;
+        for (index, line) in self.lines.iter().enumerate() {
+            if local < line.len() {
+                return Some(index);
+            }
+            local -= line.len();
+        }
+        Some(self.lines.len())
+    }
+
+    /// Offset just past everything pushed so far.
+    pub fn end_offset(&self) -> usize {
+        self.dropped_bytes + self.len
+    }
+
+    /// Position of an `end_offset` value within `text()`, or `None` once it was trimmed.
+    pub fn local_offset(&self, offset: usize) -> Option<usize> {
+        offset.checked_sub(self.dropped_bytes)
+    }
+
+    /// Remove and return the retained text.
+    pub fn take(&mut self) -> String {
+        let text = self.text();
+        self.clear();
+        text
+    }
+
+    /// Drop everything; offsets handed out earlier no longer resolve.
+    pub fn clear(&mut self) {
+        self.dropped_bytes += self.len;
+        self.lines.clear();
+        self.partial.clear();
+        self.len = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn never_exceeds_cap() {
+        let mut buffer = LogBuffer::default();
+        for i in 0..50_000 {
+            buffer.push_str(&format!("line {i}\n"), 1000);
+            assert!(buffer.line_count() <= 1000);
+        }
+        assert_eq!(buffer.line_count(), 1000);
+        assert_eq!(buffer.line(0), Some("line 49000"));
+        assert_eq!(buffer.line(999), Some("line 49999"));
+    }
+
+    #[test]
+    fn partial_line_is_completed_by_next_push() {
+        let mut buffer = LogBuffer::default();
+        buffer.push_str("abc", 10);
+        assert_eq!(buffer.line_count(), 1);
+        buffer.push_str("def\nghi", 10);
+        assert_eq!(buffer.line(0), Some("abcdef"));
+        assert_eq!(buffer.line(1), Some("ghi"));
+        assert_eq!(buffer.text(), "abcdef\nghi");
+    }
+
+    #[test]
+    fn offsets_survive_trimming() {
+        let mut buffer = LogBuffer::default();
+        buffer.push_str("one\ntwo\n", 2);
+        let three = buffer.end_offset();
+        buffer.push_str("three\n", 2);
+        // "one\n" is gone; "three" now starts after "two\n".
+        assert_eq!(buffer.local_offset(three), Some(4));
+        assert_eq!(buffer.line_index(three), Some(1));
+        assert_eq!(buffer.local_offset(0), None);
+        assert_eq!(buffer.line_index(0), None);
+        assert_eq!(buffer.line_index(buffer.end_offset()), Some(2));
+    }
+
+    #[test]
+    fn offsets_after_clear() {
+        let mut buffer = LogBuffer::default();
+        buffer.push_str("one\ntwo\n", 10);
+        let before = buffer.end_offset();
+        buffer.clear();
+        assert_eq!(buffer.line_count(), 0);
+        assert_eq!(buffer.local_offset(0), None);
+        assert_eq!(buffer.end_offset(), before);
+        assert_eq!(buffer.local_offset(before), Some(0));
+        buffer.push_str("three\n", 10);
+        assert_eq!(buffer.line_index(before), Some(0));
+        assert_eq!(buffer.text(), "three\n");
+    }
+}
@@ -0,0 +1,197 @@
+//! Mirrors the captured log to TCP clients so it can be tailed from another machine,
+//! e.g. with `nc <host> <port>`.
+
+use std::{
+    collections::VecDeque,
+    io::Write,
+    net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Condvar, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+/// How much unsent text the mirror holds for slow clients before dropping the oldest.
+const QUEUE_LIMIT_BYTES: usize = 4 * 1024 * 1024;
+
+/// Text waiting for the writer thread, capped at `QUEUE_LIMIT_BYTES`.
+#[derive(Default)]
+struct Queue {
+    texts: VecDeque<String>,
+    bytes: usize,
+    dropped_bytes: usize, // dropped since the writer last took the queue
+}
+
+impl Queue {
+    fn push(&mut self, text: &str) {
+        self.bytes += text.len();
+        self.texts.push_back(text.to_string());
+        while self.bytes > QUEUE_LIMIT_BYTES {
+            let Some(oldest) = self.texts.pop_front() else {
+                break;
+            };
+            self.bytes -= oldest.len();
+            self.dropped_bytes += oldest.len();
+        }
+    }
+
+    /// Everything queued as one write, led by a note if anything was dropped.
+    fn take(&mut self) -> String {
+        let mut text = String::with_capacity(self.bytes + 64);
+        if self.dropped_bytes > 0 {
+            text.push_str(&format!(
+                "[log mirror fell behind; {} bytes dropped]\n",
+                self.dropped_bytes
+            ));
+        }
+        text.extend(self.texts.drain(..));
+        self.bytes = 0;
+        self.dropped_bytes = 0;
+        text
+    }
+}
+
+struct Shared {
+    queue: Mutex<Queue>,
+    queued: Condvar,
+    client_count: AtomicUsize,
+    stop: AtomicBool,
+}
+
+pub struct LogMirror {
+    pub addr: SocketAddr,
+    shared: Arc<Shared>,
+}
+
+impl LogMirror {
+    /// Bind the listener, then accept clients and write to them on background threads.
+    pub fn start(port: u16, all_interfaces: bool) -> std::io::Result<Self> {
+        let host = if all_interfaces {
+            Ipv4Addr::UNSPECIFIED
+        } else {
+            Ipv4Addr::LOCALHOST
+        };
+        let listener = TcpListener::bind((host, port))?;
+        let addr = listener.local_addr()?;
+        let shared = Arc::new(Shared {
+            queue: Mutex::new(Queue::default()),
+            queued: Condvar::new(),
+            client_count: AtomicUsize::new(0),
+            stop: AtomicBool::new(false),
+        });
+        let clients = Arc::new(Mutex::new(Vec::new()));
+
+        let accepted = Arc::clone(&clients);
+        let accepting = Arc::clone(&shared);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                if accepting.stop.load(Ordering::Relaxed) {
+                    break;
+                }
+                let _ = stream.set_write_timeout(Some(Duration::from_millis(500)));
+                if let Ok(mut clients) = accepted.lock() {
+                    clients.push(stream);
+                    accepting
+                        .client_count
+                        .store(clients.len(), Ordering::Relaxed);
+                }
+            }
+        });
+
+        // Writes happen off the UI thread so a slow client can't stall rendering. Whatever
+        // piled up while writing goes out as one batch next time.
+        let writing = Arc::clone(&shared);
+        thread::spawn(move || loop {
+            let text = {
+                let Ok(mut queue) = writing.queue.lock() else {
+                    return;
+                };
+                while queue.texts.is_empty() && !writing.stop.load(Ordering::Relaxed) {
+                    queue = match writing.queued.wait(queue) {
+                        Ok(queue) => queue,
+                        Err(_) => return,
+                    };
+                }
+                if writing.stop.load(Ordering::Relaxed) {
+                    return;
+                }
+                queue.take()
+            };
+            if let Ok(mut clients) = clients.lock() {
+                clients.retain_mut(|client| client.write_all(text.as_bytes()).is_ok());
+                writing.client_count.store(clients.len(), Ordering::Relaxed);
+            }
+        });
+
+        Ok(Self { addr, shared })
+    }
+
+    /// Queue text for every connected client; disconnected clients are dropped on write.
+    /// When clients can't keep up, the oldest queued text is dropped.
+    pub fn send(&self, text: &str) {
+        if let Ok(mut queue) = self.shared.queue.lock() {
+            queue.push(text);
+            self.shared.queued.notify_one();
+        }
+    }
+
+    pub fn client_count(&self) -> usize {
+        self.shared.client_count.load(Ordering::Relaxed)
+    }
+
+    /// Whether other machines can connect, not just this one.
+    pub fn is_public(&self) -> bool {
+        !self.addr.ip().is_loopback()
+    }
+}
+
+impl Drop for LogMirror {
+    fn drop(&mut self) {
+        // Wake the blocking accept loop so it notices the stop flag and releases the port,
+        // and the writer so it exits and closes the client connections.
+        self.shared.stop.store(true, Ordering::Relaxed);
+        self.shared.queued.notify_one();
+        let _ = TcpStream::connect((Ipv4Addr::LOCALHOST, self.addr.port()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+
+    #[test]
+    fn queue_drops_oldest_text_beyond_the_limit() {
+        let mut queue = Queue::default();
+        let chunk = "x".repeat(1024 * 1024 - 1) + "\n";
+        for _ in 0..4 {
+            queue.push(&chunk);
+        }
+        queue.push("newest\n");
+        assert!(queue.bytes <= QUEUE_LIMIT_BYTES);
+        let text = queue.take();
+        assert!(text.starts_with(&format!(
+            "[log mirror fell behind; {} bytes dropped]\n",
+            chunk.len()
+        )));
+        assert!(text.ends_with("newest\n"));
+        assert_eq!(queue.take(), "");
+    }
+
+    #[test]
+    fn clients_receive_sent_text() {
+        let mirror = LogMirror::start(0, false).unwrap();
+        let client = TcpStream::connect(mirror.addr).unwrap();
+        while mirror.client_count() == 0 {
+            thread::sleep(Duration::from_millis(10));
+        }
+        mirror.send("first line\n");
+        mirror.send("second line\n");
+        let mut lines = BufReader::new(client).lines();
+        assert_eq!(lines.next().unwrap().unwrap(), "first line");
+        assert_eq!(lines.next().unwrap().unwrap(), "second line");
+        assert!(!mirror.is_public());
+    }
+}
@@ -1,45 +1,505 @@
+pub mod alert;
+pub mod ansi;
+pub mod backup;
+pub mod desktop_notification;
+pub mod detached;
+pub mod external_editor;
+pub mod file_log;
+pub mod find_replace;
+pub mod flood_guard;
+pub mod guard;
+pub mod java;
+#[cfg(target_os = "macos")]
+pub mod keychain;
+pub mod known_keys;
+pub mod log_buffer;
+pub mod log_mirror;
+pub mod notifications;
+pub mod output_tail;
+pub mod ports;
+pub mod properties;
+pub mod redact;
+pub mod run_state;
+pub mod settings_io;
+pub mod severity;
+pub mod status;
 pub mod tabs;
+pub mod theme;
+pub mod tray;
+pub mod updates;
+pub mod util;
+pub mod watchdog;
 
+use crate::app::alert::AlertSound;
+use crate::app::file_log::FileLog;
+use crate::app::find_replace::FindReplace;
+use crate::app::flood_guard::FloodGuard;
+use crate::app::log_buffer::LogBuffer;
+use crate::app::log_mirror::LogMirror;
+use crate::app::notifications::{Level, Notifications};
+use crate::app::output_tail::OutputTail;
+use crate::app::ports::PortHolder;
+use crate::app::properties::{set_value, ConfigChange, ConfigEntry};
+use crate::app::redact::Redactor;
+use crate::app::run_state::RunState;
+use crate::app::settings_io::SettingChange;
+use crate::app::severity::Severity;
+use crate::app::status::{FeedStats, StatusPoller, StatusReport};
 use crate::app::tabs::{show_config_tab, show_setup_tab, show_terminal_tab};
-use crate::model::{AppConfig, Tab};
-use eframe::egui::{self, Color32, ScrollArea, Vec2};
+use crate::app::tray::{hide_window, show_window, Tray, TrayAction};
+use crate::app::updates::Release;
+use crate::model::{
+    AppConfig, CommandMacro, ConfigViewMode, DuplicateKeyPolicy, JavaRequirement, JvmProfile, Tab,
+    Theme, WindowGeometry, DEFAULT_TIMESTAMP_FORMAT, FONT_SIZE_RANGE,
+};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local, Utc};
+use eframe::egui::{self, Color32, FontId, ScrollArea, Vec2};
 use keyring::Entry;
 use std::{
+    collections::{BTreeMap, VecDeque},
     fs,
     io::{BufRead, BufReader, Write},
-    process::{Child, Command, Stdio},
-    sync::mpsc::{channel, Receiver},
-    thread,
-    time::Duration,
+    ops::Range,
+    path::{Path, PathBuf},
+    process::{Child, ChildStdin, Command, ExitStatus, Stdio},
+    sync::{
+        mpsc::{channel, Receiver},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant, SystemTime},
 };
 
 // Import WINDOWS_1252 for fallback decoding on Windows.
 use encoding_rs::WINDOWS_1252;
+use zeroize::Zeroizing;
+
+/// How many recently used config files to remember.
+const MAX_RECENT_CONFIGS: usize = 8;
+
+/// How many error/warning lines the Recent errors panel keeps.
+const MAX_RECENT_ERRORS: usize = 20;
+
+/// How long output is held when merging stdout and stderr by receive time.
+const OUTPUT_REORDER_WINDOW_MS: i64 = 100;
+
+/// How many feed statistics samples the plots keep (ten minutes at the poll interval).
+const FEED_HISTORY_LEN: usize = 120;
+
+/// How many recent frames the frame-time readout averages.
+const FRAME_TIME_SAMPLES: usize = 60;
+
+/// Undo steps kept for the config editor.
+const CONFIG_UNDO_LIMIT: usize = 100;
+
+/// Keystrokes closer together than this are undone as one step.
+const CONFIG_UNDO_COALESCE: Duration = Duration::from_secs(1);
+
+/// Pause between lines when a macro sends several commands.
+const MACRO_LINE_DELAY: Duration = Duration::from_millis(200);
+
+/// Gap between the two size reads that decide whether a detected config is fully written.
+const AUTO_LOAD_STABILITY_GAP: Duration = Duration::from_millis(250);
+
+/// Size checks before giving up on a detected config that keeps changing.
+const AUTO_LOAD_MAX_CHECKS: u32 = 8;
+
+/// A detected config waiting to be auto-loaded until the terminal has finished writing it.
+struct PendingAutoLoad {
+    path: String,
+    due: Instant,
+    last_size: Option<u64>, // from the previous check
+    checks: u32,
+}
+
+/// Text encoding a config file was read in, so saving writes it back the same way unless
+/// the user asks for UTF-8.
+#[derive(Clone, Copy, PartialEq)]
+pub enum ConfigEncoding {
+    Utf8,
+    Windows1252,
+}
+
+impl Default for ConfigEncoding {
+    fn default() -> Self {
+        Self::Utf8
+    }
+}
+
+/// Retries of a config read that failed with a transient error, and the first wait; the
+/// wait doubles each time (100 + 200 + 400 ms).
+const CONFIG_READ_RETRIES: u32 = 3;
+const CONFIG_READ_FIRST_BACKOFF: Duration = Duration::from_millis(100);
+
+/// How long the post-save summary stays on the Config tab unless dismissed sooner.
+const SAVE_SUMMARY_DURATION: Duration = Duration::from_secs(20);
+
+/// How long teardown waits for the stdout/stderr readers to reach the end of their pipes.
+/// A grandchild holding a pipe open would otherwise block forever.
+const READER_JOIN_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Crash restarts in a row before auto-restart gives up.
+const MAX_AUTO_RESTARTS: u32 = 5;
+
+/// Start of the marker lines written for user actions; the log view colors them.
+pub const ACTION_MARKER_PREFIX: &str = "══ [";
+
+/// One line of terminal output, stamped when the reader thread received it so bursts
+/// drained in a single frame keep their real timing.
+pub struct TerminalLine {
+    pub text: String,
+    pub received_at: DateTime<Local>,
+}
+
+impl TerminalLine {
+    pub fn now(text: String) -> Self {
+        Self {
+            text,
+            received_at: Local::now(),
+        }
+    }
+}
+
+/// An error or warning line kept for the Recent errors panel.
+pub struct RecentError {
+    pub severity: Severity,
+    pub text: String,
+    pub received_at: DateTime<Local>,
+    pub log_offset: usize, // `LogBuffer` offset of the line
+}
+
+/// A Start or Reset that is waiting on the unsaved-config prompt.
+#[derive(Clone, Copy)]
+pub enum LaunchAction {
+    Start,
+    Reset,
+}
+
+/// One feed statistics poll, for the message rate and queue depth plots.
+pub struct FeedSample {
+    pub message_rate: Option<f64>, // messages per second since the previous sample
+    pub queue_depth: Option<f64>,
+}
+
+/// What the last config save wrote, shown on the Config tab for a while.
+pub struct SaveSummary {
+    pub path: String,
+    pub lines: usize,
+    pub bytes: u64,
+    pub latest_backup: Option<PathBuf>,
+    pub warnings: Vec<String>, // problems the file was saved with anyway
+    pub saved_at: Instant,
+}
+
+/// One line of the Setup tab's readiness checklist.
+pub struct ReadinessCheck {
+    pub label: &'static str,
+    pub passed: bool,
+    pub detail: String,
+    pub hint: &'static str,
+}
+
+/// Write `bytes` to a temporary file next to `target` and rename it over `target`, so an
+/// interrupted write leaves the old contents in place instead of a truncated file.
+fn write_atomically(target: &Path, bytes: &[u8]) -> std::io::Result<()> {
+    let name = target
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "no file name"))?;
+    let temp = target.with_file_name(format!(".{name}.tmp"));
+    let write = |temp: &Path| -> std::io::Result<()> {
+        let mut file = fs::File::create(temp)?;
+        file.write_all(bytes)?;
+        file.sync_all()?;
+        if let Ok(metadata) = fs::metadata(target) {
+            fs::set_permissions(temp, metadata.permissions())?;
+        }
+        fs::rename(temp, target)
+    };
+    let result = write(&temp);
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result
+}
+
+/// Keyring service holding a credential profile's entries. The default profile keeps the
+/// original service name, so credentials saved before profiles existed still load.
+fn keyring_service(profile: &str) -> String {
+    if profile.is_empty() {
+        "ThetaDataTerminal".to_string()
+    } else {
+        format!("ThetaDataTerminal-{profile}")
+    }
+}
+
+/// Whether a username and password can log in. Empty values can only come from a bad save
+/// and would start the terminal with a login it can never complete.
+pub fn credentials_usable(username: &str, password: &str) -> bool {
+    !username.trim().is_empty() && !password.is_empty()
+}
+
+/// One-line status such as `Running · CONNECTED · 2h 5m uptime` or `Stopped`, for the window
+/// title and tray tooltip. Kept free of app state so every combination is easy to check.
+pub fn format_status_summary(
+    running: bool,
+    connection: Option<&str>,
+    profile: Option<&str>,
+    uptime: Option<Duration>,
+) -> String {
+    if !running {
+        return "Stopped".to_string();
+    }
+    let mut parts = vec!["Running".to_string()];
+    parts.extend(connection.map(str::to_string));
+    parts.extend(profile.map(str::to_string));
+    parts.extend(uptime.map(|uptime| format!("{} uptime", format_duration(uptime))));
+    parts.join(" · ")
+}
+
+/// Largest two units: `1d 3h`, `2h 5m`, `12m`.
+fn format_duration(duration: Duration) -> String {
+    let minutes = duration.as_secs() / 60;
+    let (days, hours, minutes) = (minutes / 1440, minutes / 60 % 24, minutes % 60);
+    if days > 0 {
+        format!("{days}d {hours}h")
+    } else if hours > 0 {
+        format!("{hours}h {minutes}m")
+    } else {
+        format!("{minutes}m")
+    }
+}
+
+/// Clock-style `HH:MM:SS`, for the Setup tab's uptime.
+pub fn format_uptime(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+/// Errors worth retrying a file read for. NotFound, PermissionDenied and the like fail
+/// straight away.
+fn is_transient_io_error(e: &std::io::Error) -> bool {
+    use std::io::ErrorKind;
+    // ERROR_SHARING_VIOLATION and ERROR_LOCK_VIOLATION: another process has the file open.
+    const WINDOWS_SHARING_ERRORS: [i32; 2] = [32, 33];
+    matches!(
+        e.kind(),
+        ErrorKind::Interrupted | ErrorKind::WouldBlock | ErrorKind::TimedOut
+    ) || (cfg!(target_os = "windows")
+        && e.raw_os_error()
+            .is_some_and(|code| WINDOWS_SHARING_ERRORS.contains(&code)))
+}
+
+/// Whether `pattern` is a usable chrono strftime pattern. Formatting with an invalid one
+/// panics, so it is checked before every use.
+pub fn timestamp_format_is_valid(pattern: &str) -> bool {
+    !pattern.is_empty() && StrftimeItems::new(pattern).all(|item| !matches!(item, Item::Error))
+}
 
 /// The main application state and logic
 pub struct ThetaApp {
     // -- Setup tab fields --
     pub username_input: String,
     pub password_input: String,
+    pub show_password: bool, // unmask the password field; hidden again after saving
+    pub editing_credentials: bool, // saved credentials reopened for editing
     pub credentials_saved: bool,
+    pub cache_credentials_in_memory: bool,
+    cached_credentials: Option<(Zeroizing<String>, Zeroizing<String>)>, // when caching is on
+    pub profiles: Vec<String>,
+    pub profile: String,             // empty for the default profile
+    pub new_profile_name: String,    // Setup tab input
+    running_profile: Option<String>, // the named profile the running terminal was started with
 
     // -- Terminal config --
     pub jar_path: String,
+    pub java_path: String, // empty uses java/javaw from PATH
     pub auto_start: bool,
+    pub capture_output_to_file: bool, // applies on the next start
+    pub jar_test: Option<Receiver<Result<String, String>>>, // a "Test jar" run in progress
+    pub jar_test_result: Option<Result<String, String>>,
+    pub login_test: Option<Receiver<Result<String, String>>>, // a "Test Login" run in progress
+    pub login_test_result: Option<Result<String, String>>,
+    java_check: Option<Receiver<(String, Result<String, String>)>>, // `java -version` running
+    java_checked: Option<(String, Result<String, String>)>, // the java checked last and its result
+    pub confirm_start_with_unsaved_config: bool,
+    pub pending_launch: Option<LaunchAction>, // asking whether to save first
+    pub confirm_reset: bool,                  // the Reset prompt is open
+    pub confirm_quit_while_running: bool,
+    confirm_quit: bool,   // the quit prompt is open
+    quit_confirmed: bool, // let the next close request through
+    pub minimize_to_tray: bool,
+    tray: Option<Tray>,
+    tray_unavailable: bool, // creating the tray failed; not retried this session
+    hide_to_tray: bool,     // a close request is hiding the window this frame
+    pub skip_restart_confirmation: bool, // session-only; enables the ⟳ quick restart
+    pub jvm_profile: JvmProfile,
+    pub jvm_args: String, // used by the Custom profile
+    pub pass_system_timezone: bool,
+    pub system_timezone: Option<String>, // detected once at startup
+    pub jvm_arg_conflicts: Option<Vec<String>>, // shown before launching anyway
+    launch_despite_conflicts: bool,      // one-shot, set by "Start anyway"
 
     // -- Child process & logging --
+    pub headless: bool, // watchdog mode: log to stdout instead of the screen buffer
+    pub detached: bool, // --start-detached: the terminal must outlive this process
     pub process: Option<Child>,
-    pub log_text: String,
-    pub log_receiver: Option<Receiver<String>>,
+    pub started_at: Option<Instant>, // when the running process was spawned
+    pub process_pid: Option<u32>,
+    pub last_exit_code: Option<i32>, // of the last process; None if killed by a signal
+    pub run_state: RunState,         // process and feed health combined, for the Status line
+    pub auto_restart: bool,
+    stop_requested: bool, // set by Stop so the exit isn't treated as a crash
+    pub shutdown_grace_ms: u64,
+    pub on_gui_crash: GuiCrashBehavior, // applies on the next start
+    terminal_guard: Option<Child>,      // `--guard` helper for GuiCrashBehavior::StopTerminal
+    pub stop_deadline: Option<Instant>, // a graceful Stop is waiting for the process to exit
+    pub auto_restart_at: Option<Instant>, // pending crash restart; Cancel clears it
+    crash_restarts: u32,                // in a row; reset after a stable run
+    pub log_buffer: LogBuffer,
+    pub max_log_lines: usize,
+    pub mark_user_actions: bool,
+    pub strip_ansi_in_saved_log: bool,
+    pub log_receiver: Option<Receiver<TerminalLine>>,
+    reader_threads: Vec<JoinHandle<()>>, // stdout/stderr readers of the running process
+    redactor: Redactor, // masks the credentials the running terminal was started with
+    pub output_tail: Option<OutputTail>, // set while the running terminal writes to a file
+    pub stdin: Option<Arc<Mutex<ChildStdin>>>, // shared with macro sender threads
+    pub stdin_input: String,
+    pub log_filter: String, // Terminal tab search; only changes what is shown
+    pub scroll_log_to_bottom: bool, // one-shot, set when Follow output is re-enabled
+    pub macros: Vec<CommandMacro>,
+    pub port_holder: Option<PortHolder>, // result of the last port-conflict diagnosis
+    pub confirm_kill_port_holder: bool,
+    pub java_requirement: Option<JavaRequirement>,
+    pub rest_port: u16,
+    pub status_paths_text: String, // one path per line, as edited
+    pub status_poller: Option<StatusPoller>, // runs while the terminal does, or while monitoring
+    status_poller_target: Option<(String, u16)>, // host and port the poller was started for
+    pub monitoring: bool,          // session-only: poll a terminal we didn't launch
+    pub monitor_host: String,
+    pub monitor_port: u16,
+    pub terminal_status: Option<StatusReport>,
+    pub stats_path: String,
+    pub feed_history: VecDeque<FeedSample>,
+    last_feed_counter: Option<(u64, Instant)>, // messages received at the previous poll
+    pub recent_errors: VecDeque<RecentError>,  // newest last
+    pub log_jump_target: Option<usize>,        // log offset to scroll to on the next frame
+
+    // -- Daily log files --
+    pub log_to_file: bool,
+    pub log_file_wrap: bool,
+    pub log_file_wrap_width: usize,
+    pub file_log: Option<FileLog>,
+    pub file_log_error: Option<String>,
+    pub confirm_truncate_log: bool,
+    file_log_applied: bool,
+
+    // -- Remote log tail over TCP --
+    pub log_mirror_enabled: bool,
+    pub log_mirror_port: u16,
+    pub log_mirror_all_interfaces: bool,
+    pub log_mirror: Option<LogMirror>,
+    pub log_mirror_error: Option<String>,
+    log_mirror_applied: Option<(u16, bool)>, // settings the current listener was started with
+    log_mirror_notification: Option<u64>,    // the warning while it listens beyond localhost
+
+    // -- Terminal view preferences --
+    pub follow_output: bool,
+    pub wrap_log_lines: bool,
+    pub lightweight_log_view: bool,
+    pub show_timestamps: bool,
+    pub log_timestamp_format: String,
+    pub log_timestamp_utc: bool,
+    pub order_output_by_time: bool,
+    reorder_buffer: Vec<TerminalLine>, // lines held back while ordering by time
+    pub flood_threshold: usize,
+    pub alert_sound_enabled: bool,
+    pub alert_volume: f32,
+    pub notify_on_exit: bool,
+    alert_sound: AlertSound,
+    was_connected: bool, // last status report showed a live connection
+    pub flood_guard: FloodGuard,
+
+    pub readiness: Option<Vec<ReadinessCheck>>, // computed on demand
+
+    // -- Transient feedback --
+    frame_times: VecDeque<Duration>, // how long recent `update` calls took
+    pub notifications: Notifications,
+    connection_notification: Option<u64>, // the "connection lost" notice, cleared on reconnect
+
+    window_title: String, // last title handed to the window
 
     // -- Which tab is selected + the default tab --
     pub selected_tab: Tab,
     pub default_tab: Tab,
 
+    // -- Notes --
+    pub notes: String,
+
+    // -- Settings import --
+    pub pending_settings_import: Option<(AppConfig, Vec<SettingChange>)>, // awaiting confirmation
+
+    // -- Update check --
+    pub check_for_updates: bool,
+    last_update_check: Option<i64>,
+    update_check: Option<Receiver<Result<Release, String>>>,
+    pub available_update: Option<(String, String)>, // newer version and its notes URL
+    pub update_banner_dismissed: bool,
+
+    // -- Appearance --
+    pub theme: Theme,
+    applied_theme: Option<Theme>, // what was last handed to egui
+    pub font_size: f32,
+    applied_font_size: Option<f32>,
+    pub min_window_width: f32, // applies on the next launch
+    pub min_window_height: f32,
+    window_geometry: Option<WindowGeometry>, // tracked every frame, restored on launch
+    window_position_checked: bool,           // restored position checked against the monitor
+
     // -- ThetaData config file management --
     pub thetadata_config_path: String, // user's chosen config file path
     pub thetadata_config_text: String, // the text we load/edit
+    config_port_parsed: Option<(String, Option<u16>)>, // config text, its HTTP_PORT
     pub last_detected_config_path: Option<String>,
+    config_paths_compared: Option<(String, String, bool)>, // detected, edited, same file
+    pub config_saved_text: String, // contents as last loaded/saved, for dirty tracking
+    pub config_undo_stack: VecDeque<String>, // oldest first, at most CONFIG_UNDO_LIMIT
+    pub config_redo_stack: Vec<String>,
+    config_history_text: String, // the text as of the last recorded change
+    config_last_edit: Option<Instant>, // when the current run of keystrokes last changed it
+    pub config_selection: Option<Range<usize>>, // last editor selection, in bytes
+    pub auto_load_detected_config: bool,
+    pub auto_load_delay_ms: u64,
+    pending_auto_load: Option<PendingAutoLoad>,
+    pub save_panel_on_all_tabs: bool,
+    pub show_path_basenames: bool,
+    pub recent_config_paths: Vec<String>, // most recent first
+    pub pending_save_as: Option<String>,  // existing file awaiting overwrite confirmation
+    pub pending_config_load: Option<(String, &'static str)>, // path and source, awaiting discard
+    pub config_preview: Option<(String, String)>, // browsed file (path, text) awaiting Load
+    pub save_summary: Option<SaveSummary>,
+    config_on_disk: bool, // the file existed when last loaded or saved
+    pub config_encoding: ConfigEncoding, // as detected when the file was read
+    pub save_config_as_utf8: bool, // convert a Windows-1252 file on the next save
+    pub config_file_missing: bool, // it has since been deleted; asks what to do
+    pub external_editor_command: String,
+    pub backup_config_on_start: bool,
+    pub keep_backups: bool,
+    pub backup_retention: usize,
+    config_disk_mtime: Option<SystemTime>, // modification time as last loaded/saved
+    last_config_stat: Instant,
+    pub config_changed_on_disk: bool, // modified by something else; offers a reload
+    unsettled_disk_mtime: Option<SystemTime>, // a new mtime seen once, maybe mid-write
+
+    // -- Structured config editor --
+    pub config_view_mode: ConfigViewMode, // ViewOnly is read-only and hides Save
+    pub config_line_numbers: bool,
+    pub duplicate_key_policy: DuplicateKeyPolicy,
+    pub find_replace: FindReplace,
+    pub structured_edits: BTreeMap<usize, ConfigChange>, // staged edits keyed by line
+    pub pending_structured_apply: Option<Vec<ConfigChange>>, // awaiting confirmation
 }
 
 impl ThetaApp {
@@ -51,98 +511,585 @@ impl ThetaApp {
         // Always force the default tab to Setup.
         let default_tab = Tab::Setup;
 
-        let username_entry = Entry::new("ThetaDataTerminal", "username");
-        let password_entry = Entry::new("ThetaDataTerminal", "password");
-        let (username_input, password_input, credentials_saved) =
-            match (username_entry.get_password(), password_entry.get_password()) {
-                (Ok(u), Ok(_p)) => (u, String::new(), true),
-                _ => (String::new(), String::new(), false),
-            };
+        let (username_input, credentials_saved, empty_credentials) =
+            Self::read_saved_username(&cfg.profile);
 
         let jar_path = cfg.jar_path.unwrap_or_default();
         let auto_start = false; // Disable auto-start regardless of config.
         let thetadata_config_path = cfg.thetadata_config_path.unwrap_or_default();
 
+        let config_on_disk = Path::new(&thetadata_config_path).is_file();
+        let config_disk_mtime = Self::config_mtime(&thetadata_config_path);
         let mut thetadata_config_text = String::new();
+        let mut config_encoding = ConfigEncoding::default();
         if !thetadata_config_path.is_empty() {
-            thetadata_config_text =
+            (thetadata_config_text, config_encoding) =
                 Self::read_thetadata_config_file(&thetadata_config_path).unwrap_or_default();
         }
 
-        Self {
+        let mut app = Self {
             username_input,
-            password_input,
+            password_input: String::new(),
+            show_password: false,
+            editing_credentials: false,
             credentials_saved,
+            cache_credentials_in_memory: cfg.cache_credentials_in_memory,
+            cached_credentials: None,
+            profiles: cfg.profiles,
+            profile: cfg.profile,
+            new_profile_name: String::new(),
+            running_profile: None,
             jar_path,
+            java_path: cfg.java_path.unwrap_or_default(),
             auto_start,
+            capture_output_to_file: cfg.capture_output_to_file,
+            jar_test: None,
+            jar_test_result: None,
+            login_test: None,
+            login_test_result: None,
+            java_check: None,
+            java_checked: None,
+            confirm_start_with_unsaved_config: cfg.confirm_start_with_unsaved_config,
+            pending_launch: None,
+            confirm_reset: false,
+            confirm_quit_while_running: cfg.confirm_quit_while_running,
+            confirm_quit: false,
+            quit_confirmed: false,
+            minimize_to_tray: cfg.minimize_to_tray,
+            tray: None,
+            tray_unavailable: false,
+            hide_to_tray: false,
+            skip_restart_confirmation: false,
+            jvm_profile: cfg.jvm_profile,
+            jvm_args: cfg.jvm_args,
+            pass_system_timezone: cfg.pass_system_timezone,
+            system_timezone: java::system_timezone(),
+            jvm_arg_conflicts: None,
+            launch_despite_conflicts: false,
+            headless: false,
+            detached: false,
             process: None,
-            log_text: String::new(),
+            started_at: None,
+            process_pid: None,
+            last_exit_code: None,
+            run_state: RunState::default(),
+            auto_restart: cfg.auto_restart,
+            stop_requested: false,
+            shutdown_grace_ms: cfg.shutdown_grace_ms,
+            on_gui_crash: cfg.on_gui_crash,
+            terminal_guard: None,
+            stop_deadline: None,
+            auto_restart_at: None,
+            crash_restarts: 0,
+            log_buffer: LogBuffer::default(),
+            max_log_lines: cfg.max_log_lines,
+            mark_user_actions: cfg.mark_user_actions,
+            strip_ansi_in_saved_log: cfg.strip_ansi_in_saved_log,
             log_receiver: None,
+            reader_threads: Vec::new(),
+            redactor: Redactor::default(),
+            output_tail: None,
+            stdin: None,
+            stdin_input: String::new(),
+            log_filter: String::new(),
+            scroll_log_to_bottom: false,
+            macros: cfg.macros,
+            port_holder: None,
+            confirm_kill_port_holder: false,
+            java_requirement: cfg.java_requirement,
+            rest_port: cfg.rest_port,
+            status_paths_text: cfg.status_paths.join("\n"),
+            status_poller: None,
+            status_poller_target: None,
+            monitoring: false,
+            monitor_host: cfg.monitor_host,
+            monitor_port: cfg.monitor_port,
+            terminal_status: None,
+            stats_path: cfg.stats_path,
+            feed_history: VecDeque::new(),
+            last_feed_counter: None,
+            recent_errors: VecDeque::new(),
+            log_jump_target: None,
+            log_to_file: cfg.log_to_file,
+            log_file_wrap: cfg.log_file_wrap,
+            log_file_wrap_width: cfg.log_file_wrap_width,
+            file_log: None,
+            file_log_error: None,
+            confirm_truncate_log: false,
+            file_log_applied: false,
+            log_mirror_enabled: cfg.log_mirror_enabled,
+            log_mirror_port: cfg.log_mirror_port,
+            log_mirror_all_interfaces: cfg.log_mirror_all_interfaces,
+            log_mirror: None,
+            log_mirror_error: None,
+            log_mirror_applied: None,
+            log_mirror_notification: None,
+            follow_output: cfg.follow_output,
+            wrap_log_lines: cfg.wrap_log_lines,
+            lightweight_log_view: cfg.lightweight_log_view,
+            show_timestamps: cfg.show_timestamps,
+            log_timestamp_format: cfg.log_timestamp_format,
+            log_timestamp_utc: cfg.log_timestamp_utc,
+            order_output_by_time: cfg.order_output_by_time,
+            reorder_buffer: Vec::new(),
+            flood_threshold: cfg.flood_threshold,
+            alert_sound_enabled: cfg.alert_sound_enabled,
+            alert_volume: cfg.alert_volume,
+            notify_on_exit: cfg.notify_on_exit,
+            alert_sound: AlertSound::default(),
+            was_connected: false,
+            flood_guard: FloodGuard::default(),
+            readiness: None,
+            frame_times: VecDeque::with_capacity(FRAME_TIME_SAMPLES),
+            notifications: Notifications::default(),
+            connection_notification: None,
+            window_title: String::new(),
             selected_tab: default_tab,
             default_tab,
+            theme: cfg.theme,
+            font_size: cfg.font_size,
+            applied_font_size: None,
+            applied_theme: None,
+            notes: cfg.notes,
+            check_for_updates: cfg.check_for_updates,
+            last_update_check: cfg.last_update_check,
+            update_check: None,
+            available_update: cfg.available_update.zip(cfg.available_update_url),
+            update_banner_dismissed: false,
+            pending_settings_import: None,
+            min_window_width: cfg.min_window_width,
+            min_window_height: cfg.min_window_height,
+            window_geometry: cfg.window,
+            window_position_checked: false,
             thetadata_config_path,
+            config_saved_text: thetadata_config_text.clone(),
+            config_undo_stack: VecDeque::new(),
+            config_redo_stack: Vec::new(),
+            config_history_text: thetadata_config_text.clone(),
+            config_last_edit: None,
+            config_selection: None,
+            config_encoding,
+            save_config_as_utf8: false,
             thetadata_config_text,
+            config_port_parsed: None,
             last_detected_config_path: None,
+            config_paths_compared: None,
+            auto_load_detected_config: cfg.auto_load_detected_config,
+            auto_load_delay_ms: cfg.auto_load_delay_ms,
+            pending_auto_load: None,
+            save_panel_on_all_tabs: cfg.save_panel_on_all_tabs,
+            show_path_basenames: cfg.show_path_basenames,
+            recent_config_paths: cfg.recent_config_paths,
+            pending_save_as: None,
+            pending_config_load: None,
+            config_preview: None,
+            save_summary: None,
+            backup_config_on_start: cfg.backup_config_on_start,
+            keep_backups: cfg.keep_backups,
+            backup_retention: cfg.backup_retention,
+            config_on_disk,
+            config_file_missing: false,
+            external_editor_command: cfg.external_editor_command,
+            config_disk_mtime,
+            last_config_stat: Instant::now(),
+            config_changed_on_disk: false,
+            unsettled_disk_mtime: None,
+            config_view_mode: cfg.config_view_mode,
+            config_line_numbers: cfg.config_line_numbers,
+            duplicate_key_policy: cfg.duplicate_key_policy,
+            find_replace: FindReplace::default(),
+            structured_edits: BTreeMap::new(),
+            pending_structured_apply: None,
+        };
+        if !timestamp_format_is_valid(&app.log_timestamp_format) {
+            app.append_log(&format!(
+                "Warning: invalid log timestamp format \"{}\"; using {DEFAULT_TIMESTAMP_FORMAT}.\n",
+                app.log_timestamp_format
+            ));
+        }
+        if empty_credentials {
+            app.add_notification(
+                Level::Warning,
+                "The saved username or password is empty. Please enter and save your \
+                 credentials again.",
+            );
+        }
+        app
+    }
+
+    /// Start or reset the terminal, first asking to save unsaved config edits, which the
+    /// terminal would otherwise not see.
+    pub fn request_launch(&mut self, action: LaunchAction) {
+        if matches!(action, LaunchAction::Start) && self.process.is_some() {
+            return;
+        }
+        let unsaved = self.config_is_dirty() && !self.thetadata_config_path.is_empty();
+        if self.confirm_start_with_unsaved_config && unsaved {
+            self.pending_launch = Some(action);
+        } else {
+            self.launch(action);
+        }
+    }
+
+    /// Reset kills the running terminal, so ask first; a stopped terminal just starts.
+    pub fn request_reset(&mut self) {
+        if self.process.is_some() {
+            self.confirm_reset = true;
+        } else {
+            self.request_launch(LaunchAction::Reset);
+        }
+    }
+
+    /// The one-click restart, only honoured once the user has opted out of the prompt.
+    pub fn quick_restart(&mut self) {
+        if self.skip_restart_confirmation {
+            self.request_launch(LaunchAction::Reset);
+        }
+    }
+
+    fn launch(&mut self, action: LaunchAction) {
+        match action {
+            LaunchAction::Start => self.start_terminal(),
+            LaunchAction::Reset => self.reset_terminal(),
+        }
+    }
+
+    /// The configured Java executable, or `java` from PATH, for version checks and tests.
+    fn java_program(&self) -> &str {
+        if self.java_path.is_empty() {
+            "java"
+        } else {
+            &self.java_path
+        }
+    }
+
+    /// Check that the jar runs under the installed Java by asking it for its version on a
+    /// short-lived process of its own; the running terminal, if any, is left alone.
+    pub fn test_jar(&mut self) {
+        if self.jar_path.is_empty() {
+            self.jar_test_result = Some(Err("No jar path set".to_string()));
+            return;
+        }
+        let jar_path = self.jar_path.clone();
+        let java = self.java_program().to_string();
+        let (tx, rx) = channel();
+        thread::spawn(move || {
+            let _ = tx.send(java::test_jar(&java, &jar_path));
+        });
+        self.jar_test = Some(rx);
+        self.jar_test_result = None;
+    }
+
+    /// Run `java -version` for the configured Java on a background thread; `poll_java_check`
+    /// picks up the result. Does nothing while a check is already running.
+    pub fn refresh_java_check(&mut self) {
+        if self.java_check.is_some() {
+            return;
+        }
+        let java = self.java_program().to_string();
+        let (tx, rx) = channel();
+        thread::spawn(move || {
+            let result = java::check_java_available(&java);
+            let _ = tx.send((java, result));
+        });
+        self.java_check = Some(rx);
+    }
+
+    /// Store a finished Java check, and start a new one when the configured Java has not
+    /// been checked yet (at launch, or after the Java path changed).
+    fn poll_java_check(&mut self) {
+        if let Some(checked) = self.java_check.as_ref().and_then(|rx| rx.try_recv().ok()) {
+            self.java_check = None;
+            self.java_checked = Some(checked);
+            // Rebuilt with the new result the next time the Setup tab is shown.
+            self.readiness = None;
+        }
+        if self.java_version().is_none() {
+            self.refresh_java_check();
+        }
+    }
+
+    /// The last `java -version` result for the configured Java, if it has been checked.
+    pub fn java_version(&self) -> Option<&Result<String, String>> {
+        self.java_checked
+            .as_ref()
+            .filter(|(java, _)| java == self.java_program())
+            .map(|(_, result)| result)
+    }
+
+    fn poll_jar_test(&mut self) {
+        let Some(result) = self.jar_test.as_ref().and_then(|rx| rx.try_recv().ok()) else {
+            return;
+        };
+        self.jar_test = None;
+        match &result {
+            Ok(version) => self.append_log(&format!("Test jar: {version}\n")),
+            Err(e) => self.append_log(&format!("Test jar failed: {e}\n")),
+        }
+        self.jar_test_result = Some(result);
+    }
+
+    /// Check credentials with a short terminal run that is killed once it reports the login
+    /// result. Uses the entered password when there is one, otherwise the saved credentials.
+    pub fn test_login(&mut self) {
+        let problem = if self.jar_path.is_empty() {
+            Some("No jar path set")
+        } else if self.process.is_some() {
+            Some("Stop the terminal first; a second one would clash with it")
+        } else {
+            None
+        };
+        if let Some(problem) = problem {
+            self.login_test_result = Some(Err(problem.to_string()));
+            return;
+        }
+        let credentials = if self.password_input.is_empty() {
+            self.load_credentials()
+        } else {
+            Some((
+                Zeroizing::new(self.username_input.clone()),
+                Zeroizing::new(self.password_input.clone()),
+            ))
+        };
+        let Some((username, password)) = credentials.filter(|(u, p)| credentials_usable(u, p))
+        else {
+            self.login_test_result = Some(Err("Enter a username and password first".to_string()));
+            return;
+        };
+        let jvm_args = java::jvm_profile_flags(self.jvm_profile, &self.jvm_args);
+        let java = self.java_program().to_string();
+        let jar_path = self.jar_path.clone();
+        let (tx, rx) = channel();
+        thread::spawn(move || {
+            let _ = tx.send(java::test_login(
+                &java, &jar_path, &jvm_args, &username, &password,
+            ));
+        });
+        self.append_log("Testing login…\n");
+        self.login_test = Some(rx);
+        self.login_test_result = None;
+    }
+
+    fn poll_login_test(&mut self) {
+        let Some(result) = self.login_test.as_ref().and_then(|rx| rx.try_recv().ok()) else {
+            return;
+        };
+        self.login_test = None;
+        match &result {
+            Ok(line) => self.append_log(&format!("Test login succeeded: {line}\n")),
+            Err(e) => self.append_log(&format!("Test login failed: {e}\n")),
         }
+        self.login_test_result = Some(result);
     }
 
     /// Start the Theta Terminal process if not already running.
     pub fn start_terminal(&mut self) {
+        if self.login_test.is_some() {
+            self.add_notification(
+                Level::Warning,
+                "Wait for the login test to finish before starting the terminal.",
+            );
+            return;
+        }
         if self.process.is_none() && !self.jar_path.is_empty() {
-            let username_entry = Entry::new("ThetaDataTerminal", "username");
-            let password_entry = Entry::new("ThetaDataTerminal", "password");
+            if !self.java_path.is_empty() && !Path::new(&self.java_path).is_file() {
+                self.add_notification(
+                    Level::Error,
+                    &format!(
+                        "Java executable not found: {}. Fix it on the Setup tab or clear it to use \
+                         the one on PATH.",
+                        self.java_path
+                    ),
+                );
+                return;
+            }
+            // Checked up front: a missing java only shows up as a bare spawn error. Uses the
+            // background check; until that has finished, the launch goes ahead unchecked.
+            let java_checked = self.java_version().cloned();
+            // Notice a Java installed or upgraded since, in time for the next launch.
+            self.refresh_java_check();
+            match java_checked {
+                Some(Ok(version)) if !self.java_meets_known_requirement(&version) => return,
+                Some(Err(e)) => {
+                    self.add_notification(
+                        Level::Error,
+                        &format!("{e} — install a JRE 11+ to run the terminal."),
+                    );
+                    return;
+                }
+                _ => {}
+            }
 
-            if let (Ok(username), Ok(password)) =
-                (username_entry.get_password(), password_entry.get_password())
-            {
-                let mut command = if cfg!(target_os = "windows") {
+            if let Some((username, password)) = self.load_credentials() {
+                if !credentials_usable(&username, &password) {
+                    self.add_notification(
+                        Level::Warning,
+                        "Saved credentials are empty. Re-enter them on the Setup tab.",
+                    );
+                    return;
+                }
+                let mut jvm_args = java::jvm_profile_flags(self.jvm_profile, &self.jvm_args);
+                if self.pass_system_timezone
+                    && !jvm_args
+                        .iter()
+                        .any(|arg| arg.starts_with("-Duser.timezone="))
+                {
+                    // Left out if the zone is unknown; the Setup tab says so.
+                    if let Some(zone) = &self.system_timezone {
+                        jvm_args.push(format!("-Duser.timezone={zone}"));
+                    }
+                }
+                let conflicts = java::conflicting_jvm_args(&jvm_args, &username, &password);
+                let acknowledged = std::mem::take(&mut self.launch_despite_conflicts);
+                if !conflicts.is_empty() && !acknowledged {
+                    self.append_log(&format!(
+                        "JVM arguments conflict with what the app manages: {}\n",
+                        conflicts.join("; ")
+                    ));
+                    // Headless there is nobody to ask, so warn and carry on.
+                    if !self.headless {
+                        self.jvm_arg_conflicts = Some(conflicts);
+                        return;
+                    }
+                }
+                if self.backup_config_on_start {
+                    self.backup_config_before_start();
+                }
+                let mut command = if !self.java_path.is_empty() {
+                    Command::new(&self.java_path)
+                } else if cfg!(target_os = "windows") {
                     // Use javaw on Windows so no console window is created.
                     Command::new("javaw")
                 } else {
                     Command::new("java")
                 };
                 command
+                    .args(jvm_args)
                     .arg("-jar")
                     .arg(&self.jar_path)
-                    .arg(&username)
-                    .arg(&password)
-                    .stdout(Stdio::piped())
-                    .stderr(Stdio::piped());
+                    .arg(username.as_str())
+                    .arg(password.as_str());
+                if self.detached {
+                    // Nothing will be left to write to a pipe once this process exits.
+                    command.stdin(Stdio::null());
+                } else {
+                    command.stdin(Stdio::piped());
+                }
+                // Its own process group, so a Ctrl+C or hangup that takes down this process
+                // doesn't reach it: detached, or meant to survive a GUI crash.
+                let own_group =
+                    self.detached || self.on_gui_crash == GuiCrashBehavior::LeaveRunning;
+                #[cfg(unix)]
+                {
+                    use std::os::unix::process::CommandExt;
+                    if own_group {
+                        command.process_group(0);
+                    }
+                }
+
+                // Either pipe output into the app, or let the terminal write straight to a
+                // file that we tail on a throttled schedule (much cheaper under heavy load).
+                // A detached terminal outlives our pipes, so it always writes to the file.
+                let output_path = if self.capture_output_to_file || self.detached {
+                    match Self::open_terminal_output_file() {
+                        Ok((path, stdout_file, stderr_file)) => {
+                            command
+                                .stdout(Stdio::from(stdout_file))
+                                .stderr(Stdio::from(stderr_file));
+                            Some(path)
+                        }
+                        Err(e) => {
+                            self.add_notification(
+                                Level::Error,
+                                &format!("Failed to open terminal output file: {e}"),
+                            );
+                            return;
+                        }
+                    }
+                } else {
+                    command.stdout(Stdio::piped()).stderr(Stdio::piped());
+                    None
+                };
                 #[cfg(target_os = "windows")]
                 {
                     use std::os::windows::process::CommandExt;
                     const CREATE_NO_WINDOW: u32 = 0x08000000;
-                    // Optionally, you could also add DETACHED_PROCESS: 0x00000008
-                    command.creation_flags(CREATE_NO_WINDOW);
+                    const DETACHED_PROCESS: u32 = 0x00000008;
+                    const CREATE_NEW_PROCESS_GROUP: u32 = 0x00000200;
+                    if self.detached {
+                        command.creation_flags(DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP);
+                    } else if own_group {
+                        command.creation_flags(CREATE_NO_WINDOW | CREATE_NEW_PROCESS_GROUP);
+                    } else {
+                        command.creation_flags(CREATE_NO_WINDOW);
+                    }
                 }
                 match command.spawn() {
                     Ok(mut child) => {
+                        self.stdin = child.stdin.take().map(|s| Arc::new(Mutex::new(s)));
                         let (tx, rx) = channel();
                         if let Some(stdout) = child.stdout.take() {
                             let tx_stdout = tx.clone();
-                            thread::spawn(move || {
+                            self.reader_threads.push(thread::spawn(move || {
                                 let reader = BufReader::new(stdout);
                                 for line in reader.lines().flatten() {
-                                    let _ = tx_stdout.send(line);
+                                    let _ = tx_stdout.send(TerminalLine::now(line));
                                 }
-                            });
+                            }));
                         }
                         if let Some(stderr) = child.stderr.take() {
-                            thread::spawn(move || {
+                            self.reader_threads.push(thread::spawn(move || {
                                 let reader = BufReader::new(stderr);
                                 for line in reader.lines().flatten() {
-                                    let _ = tx.send(line);
+                                    let _ = tx.send(TerminalLine::now(line));
                                 }
-                            });
+                            }));
                         }
                         self.log_receiver = Some(rx);
+                        self.process_pid = Some(child.id());
+                        if !self.detached && self.on_gui_crash == GuiCrashBehavior::StopTerminal {
+                            match guard::spawn(child.id()) {
+                                Ok(guard) => self.terminal_guard = Some(guard),
+                                Err(e) => {
+                                    self.add_notification(
+                                        Level::Warning,
+                                        &format!(
+                                            "Failed to start the crash guard; the terminal will \
+                                             keep running if this app crashes: {e}"
+                                        ),
+                                    );
+                                }
+                            }
+                        }
+                        self.redactor = Redactor::new([username.as_str(), password.as_str()]);
                         self.process = Some(child);
+                        self.started_at = Some(Instant::now());
+                        self.running_profile = Some(self.profile.clone()).filter(|p| !p.is_empty());
+                        self.last_exit_code = None;
+                        self.recent_errors.clear();
+                        // A reset keeps the poller; its last report was about the old process.
+                        self.run_state = RunState::Starting;
+                        self.stop_requested = false;
+                        self.terminal_status = None;
+                        self.was_connected = false;
                         self.append_log("Terminal started.\n");
+                        if let Some(path) = output_path {
+                            self.append_log(&format!(
+                                "Terminal output is being written to {}\n",
+                                path.display()
+                            ));
+                            self.output_tail = Some(OutputTail::new(path));
+                        }
+                    }
+                    Err(e) => {
+                        self.add_notification(
+                            Level::Error,
+                            &format!("Failed to start terminal: {e}"),
+                        );
                     }
-                    Err(e) => self.append_log(&format!("Failed to start terminal: {e}\n")),
                 }
             } else {
-                self.append_log("No valid credentials found. Cannot start.\n");
+                self.add_notification(Level::Warning, "No valid credentials found. Cannot start.");
             }
         }
     }
@@ -150,127 +1097,2395 @@ impl ThetaApp {
     /// Forcefully quit the terminal process.
     pub fn force_quit_process(&mut self) {
         if let Some(mut child) = self.process.take() {
+            self.stop_terminal_guard();
+            self.stop_deadline = None;
+            self.started_at = None;
+            self.process_pid = None;
+            self.stdin = None;
             let _ = child.kill();
-            let _ = child.wait();
+            self.last_exit_code = child.wait().ok().and_then(|status| status.code());
+            self.finish_readers();
+            self.finish_output_tail();
+            self.redactor = Redactor::default();
             self.append_log("Terminal forcibly quit.\n");
         }
     }
 
-    pub fn stop_terminal(&mut self) {
-        self.force_quit_process();
+    /// Dismiss the crash guard before the terminal goes away on purpose, so it can't later
+    /// kill a process that reused the PID.
+    fn stop_terminal_guard(&mut self) {
+        if let Some(mut guard) = self.terminal_guard.take() {
+            let _ = guard.kill();
+            let _ = guard.wait();
+        }
     }
 
-    pub fn reset_terminal(&mut self) {
-        self.force_quit_process();
-        thread::sleep(Duration::from_millis(250));
-        self.start_terminal();
+    /// Location of the file the terminal writes to when output capture is redirected.
+    pub fn terminal_output_path() -> Option<PathBuf> {
+        Self::app_data_dir().map(|dir| dir.join("terminal_output.log"))
     }
 
-    pub fn save_credentials(&mut self) {
-        let username_entry = Entry::new("ThetaDataTerminal", "username");
-        let password_entry = Entry::new("ThetaDataTerminal", "password");
+    /// Directory holding the app's config file, also used for its logs and other data.
+    pub fn app_data_dir() -> Option<PathBuf> {
+        let config_file = confy::get_configuration_file_path("thetadata_terminal_manager", None);
+        config_file.ok()?.parent().map(Path::to_path_buf)
+    }
 
-        if let (Ok(()), Ok(())) = (
-            username_entry.set_password(&self.username_input),
-            password_entry.set_password(&self.password_input),
-        ) {
-            self.credentials_saved = true;
-            self.append_log("Credentials saved.\n");
-        } else {
-            self.append_log("Failed to save credentials.\n");
+    /// Create (truncating) the output file and return one handle each for stdout and stderr.
+    /// Both append, so interleaved writes never overwrite each other and the writes carry on
+    /// at the start once `OutputTail` empties the file.
+    fn open_terminal_output_file() -> std::io::Result<(PathBuf, fs::File, fs::File)> {
+        let path = Self::terminal_output_path()
+            .ok_or_else(|| std::io::Error::other("no app config directory"))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
         }
+        fs::File::create(&path)?;
+        let stdout_file = fs::OpenOptions::new().append(true).open(&path)?;
+        let stderr_file = stdout_file.try_clone()?;
+        Ok((path, stdout_file, stderr_file))
     }
 
-    pub fn remove_credentials(&mut self) {
-        let username_entry = Entry::new("ThetaDataTerminal", "username");
-        let password_entry = Entry::new("ThetaDataTerminal", "password");
-
-        let _ = username_entry.delete_password();
-        let _ = password_entry.delete_password();
-        self.username_input.clear();
-        self.password_input.clear();
-        self.credentials_saved = false;
-        self.append_log("Credentials removed.\n");
+    /// Once the process is gone: wait (up to `READER_JOIN_TIMEOUT`) for the pipe readers to
+    /// hit end of file, then show whatever they delivered since the last frame. The final
+    /// lines are often the crash reason, so they must not be dropped with the channel.
+    fn finish_readers(&mut self) {
+        let deadline = Instant::now() + READER_JOIN_TIMEOUT;
+        while self.reader_threads.iter().any(|t| !t.is_finished()) && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+        // A reader still blocked after the timeout is left to exit on its own.
+        for reader in std::mem::take(&mut self.reader_threads) {
+            if reader.is_finished() {
+                let _ = reader.join();
+            }
+        }
+        let mut lines = std::mem::take(&mut self.reorder_buffer);
+        if let Some(rx) = self.log_receiver.take() {
+            lines.extend(rx.try_iter());
+        }
+        for line in lines {
+            self.ingest_terminal_line(line, true);
+        }
     }
 
-    pub fn append_log(&mut self, text: &str) {
-        self.log_text.push_str(text);
+    /// Read the last lines from the redirected output file and stop tailing it.
+    fn finish_output_tail(&mut self) {
+        if let Some(mut tail) = self.output_tail.take() {
+            if let Ok(lines) = tail.poll(true) {
+                for line in lines {
+                    self.ingest_terminal_line(TerminalLine::now(line), true);
+                }
+            }
+        }
     }
 
-    /// Detect and capture a config file path from a log line.
-    pub fn detect_config_file_path_in_line(&mut self, line: &str) {
-        let prefix = "Using ";
-        let suffix = " as the config file";
-        if let (Some(start_idx), Some(end_idx)) = (line.find(prefix), line.find(suffix)) {
-            let path_start = start_idx + prefix.len();
-            if path_start < end_idx {
-                let raw_path = &line[path_start..end_idx].trim();
-                self.last_detected_config_path = Some(raw_path.to_string());
-                self.append_log(&format!(
-                    "Detected config file path from terminal: {raw_path}\n"
-                ));
+    /// Send one line to the terminal's stdin.
+    pub fn send_stdin_line(&mut self, line: &str) {
+        let Some(stdin) = self.stdin.clone() else {
+            self.add_notification(
+                Level::Warning,
+                "Terminal is not running; nothing to send input to.",
+            );
+            return;
+        };
+        let result = match stdin.lock() {
+            Ok(mut stdin) => writeln!(stdin, "{line}").and_then(|_| stdin.flush()),
+            Err(_) => return,
+        };
+        match result {
+            Ok(()) => self.append_log(&format!("> {line}\n")),
+            Err(e) => {
+                self.add_notification(
+                    Level::Error,
+                    &format!("Failed to write to terminal stdin: {e}"),
+                );
             }
         }
     }
 
-    /// Read the ThetaData config file.
-    /// If the file isn’t valid UTF‑8, decode it as Windows‑1252.
-    pub fn read_thetadata_config_file(path: &str) -> std::io::Result<String> {
-        let bytes = fs::read(path)?;
-        match String::from_utf8(bytes.clone()) {
-            Ok(s) => Ok(s),
-            Err(_) => {
-                let (cow, _, _) = WINDOWS_1252.decode(&bytes);
-                Ok(cow.into_owned())
+    /// Send each non-empty line of a macro to stdin, pausing briefly between lines.
+    pub fn run_macro(&mut self, index: usize) {
+        let Some(command_macro) = self.macros.get(index).cloned() else {
+            return;
+        };
+        let Some(stdin) = self.stdin.clone() else {
+            self.add_notification(Level::Warning, "Terminal is not running; macro not sent.");
+            return;
+        };
+        let lines: Vec<String> = command_macro
+            .commands
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(str::to_string)
+            .collect();
+        self.append_log(&format!(
+            "Running macro '{}' ({} line(s)).\n",
+            command_macro.name,
+            lines.len()
+        ));
+        thread::spawn(move || {
+            for (i, line) in lines.iter().enumerate() {
+                if i > 0 {
+                    thread::sleep(MACRO_LINE_DELAY);
+                }
+                let Ok(mut writer) = stdin.lock() else {
+                    return;
+                };
+                if writeln!(writer, "{line}")
+                    .and_then(|_| writer.flush())
+                    .is_err()
+                {
+                    return;
+                }
             }
-        }
+        });
     }
 
-    /// Write the ThetaData config file.
-    pub fn write_thetadata_config_file(path: &str, contents: &str) -> std::io::Result<()> {
-        let mut file = fs::File::create(path)?;
-        file.write_all(contents.as_bytes())?;
-        Ok(())
+    /// The config-related parts of how `start_terminal` launches the jar, for reproducing
+    /// the launch by hand. The GUI passes no config argument and sets no working directory,
+    /// so the terminal resolves its config itself from the inherited directory.
+    pub fn config_launch_args(&self) -> String {
+        let cwd = std::env::current_dir()
+            .map(|dir| dir.display().to_string())
+            .unwrap_or_else(|e| format!("<unknown: {e}>"));
+        let detected = self
+            .last_detected_config_path
+            .as_deref()
+            .unwrap_or("not detected yet (start the terminal once)");
+        let loaded = if self.thetadata_config_path.is_empty() {
+            "none"
+        } else {
+            self.thetadata_config_path.as_str()
+        };
+        format!(
+            "# Working directory (inherited from the GUI)\n\
+             cd \"{cwd}\"\n\
+             # No config argument is passed; ThetaTerminal picks its config itself.\n\
+             # Config the terminal reported using: {detected}\n\
+             # Config open in the editor: {loaded}\n"
+        )
     }
 
-    /// Save the current config file text.
-    pub fn save_current_config_file(&mut self) {
-        if self.thetadata_config_path.is_empty() {
-            self.append_log("No config file path set.\n");
+    /// Snapshot the config the terminal is about to read, since it may rewrite the file on
+    /// startup. A failed backup is logged but doesn't block the start.
+    fn backup_config_before_start(&mut self) {
+        let path = Path::new(&self.thetadata_config_path);
+        if self.thetadata_config_path.is_empty() || !path.is_file() {
             return;
         }
-        match Self::write_thetadata_config_file(
-            &self.thetadata_config_path,
-            &self.thetadata_config_text,
-        ) {
-            Ok(_) => self.append_log("Config file saved.\n"),
-            Err(e) => self.append_log(&format!("Failed to write config file: {e}\n")),
+        match backup::backup_file(path, self.backup_retention) {
+            Ok(backup) => self.append_log(&format!("Backed up config to {}\n", backup.display())),
+            Err(e) => {
+                self.add_notification(
+                    Level::Warning,
+                    &format!("Config backup before start failed: {e}"),
+                );
+            }
         }
     }
-}
 
-impl eframe::App for ThetaApp {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        // Only show the bottom panel (with Save button) when on the Config tab.
-        if self.selected_tab == Tab::Config {
-            eframe::egui::TopBottomPanel::bottom("global_bottom_panel").show(ctx, |ui| {
-                ui.add_space(6.0);
-                if ui.button("Save").clicked() {
-                    self.save_current_config_file();
-                }
-                ui.add_space(6.0);
-            });
+    /// Keep a copy of the file a save is about to replace. A failed backup is logged but
+    /// doesn't block the save, which can't truncate the file anyway.
+    fn backup_config_before_save(&mut self) {
+        let path = Path::new(&self.thetadata_config_path);
+        if !path.is_file() {
+            return;
         }
+        match backup::backup_file(path, self.backup_retention) {
+            Ok(backup) => self.append_log(&format!(
+                "Backed up config to {} before saving.\n",
+                backup.display()
+            )),
+            Err(e) => {
+                self.add_notification(
+                    Level::Warning,
+                    &format!("Config backup before save failed: {e}"),
+                );
+            }
+        }
+    }
 
-        eframe::egui::CentralPanel::default().show(ctx, |ui| {
-            ui.add_space(4.0);
-            ui.heading(egui::RichText::new("ThetaData GUI Wrapper").strong());
-            ui.add_space(8.0);
+    /// Ask the terminal to exit and give it `shutdown_grace_ms` before killing it; see
+    /// `poll_graceful_stop`. A second Stop while waiting kills it right away.
+    pub fn stop_terminal(&mut self) {
+        self.stop_requested = true;
+        self.auto_restart_at = None;
+        let Some(pid) = self.process_pid else {
+            self.force_quit_process();
+            return;
+        };
+        if self.stop_deadline.is_some() || self.shutdown_grace_ms == 0 {
+            self.stop_deadline = None;
+            self.force_quit_process();
+            return;
+        }
+        // Closing stdin is the only polite signal a windowless javaw can receive; elsewhere
+        // it just goes along with SIGTERM.
+        self.stdin = None;
+        if let Err(e) = ports::request_terminate(pid) {
+            self.add_notification(
+                Level::Error,
+                &format!("Failed to ask the terminal to exit: {e}"),
+            );
+        }
+        self.append_log(&format!(
+            "Stopping terminal (waiting up to {} ms)…\n",
+            self.shutdown_grace_ms
+        ));
+        self.stop_deadline = Some(Instant::now() + Duration::from_millis(self.shutdown_grace_ms));
+    }
 
-            ui.with_layout(
-                egui::Layout::top_down_justified(egui::Align::Center),
-                |ui| {
-                    ui.horizontal(|ui| {
+    /// Kill the terminal if it hasn't exited by the end of a graceful Stop's grace period.
+    fn poll_graceful_stop(&mut self) {
+        if self.stop_deadline.is_some_and(|at| Instant::now() >= at) {
+            self.stop_deadline = None;
+            self.add_notification(
+                Level::Warning,
+                &format!(
+                    "Terminal did not exit within {} ms; forcing it.",
+                    self.shutdown_grace_ms
+                ),
+            );
+            self.force_quit_process();
+        }
+    }
+
+    /// After the terminal exits on its own with a failure, schedule a restart with the
+    /// watchdog's backoff, giving up after `MAX_AUTO_RESTARTS` in a row.
+    fn schedule_crash_restart(&mut self, status: ExitStatus) {
+        if !self.auto_restart || status.success() || self.stop_requested {
+            return;
+        }
+        if self.crash_restarts >= MAX_AUTO_RESTARTS {
+            self.add_notification(
+                Level::Error,
+                &format!(
+                    "Terminal crashed {MAX_AUTO_RESTARTS} times in a row; not restarting again."
+                ),
+            );
+            return;
+        }
+        self.crash_restarts += 1;
+        let delay = watchdog::restart_delay(self.crash_restarts);
+        self.add_notification(
+            Level::Warning,
+            &format!(
+                "Terminal crashed; restarting in {}s (attempt {} of {MAX_AUTO_RESTARTS}).",
+                delay.as_secs(),
+                self.crash_restarts
+            ),
+        );
+        self.auto_restart_at = Some(Instant::now() + delay);
+    }
+
+    fn poll_crash_restart(&mut self) {
+        if self.auto_restart_at.is_some_and(|at| Instant::now() >= at) {
+            self.auto_restart_at = None;
+            if self.process.is_none() {
+                self.start_terminal();
+            }
+        }
+        if self
+            .started_at
+            .is_some_and(|started| started.elapsed() >= watchdog::STABLE_RUN)
+        {
+            self.crash_restarts = 0;
+        }
+    }
+
+    pub fn reset_terminal(&mut self) {
+        self.force_quit_process();
+        thread::sleep(Duration::from_millis(250));
+        self.start_terminal();
+    }
+
+    /// On macOS, a failed keychain round-trip usually means the build lacks entitlements or
+    /// the app hasn't been granted access, not that the user entered something wrong. It
+    /// writes to the keychain, so it only runs on request or after a keychain write failed.
+    #[cfg(target_os = "macos")]
+    fn keychain_self_test() -> Option<String> {
+        keychain::round_trip("ThetaDataTerminal").err().map(|e| {
+            format!(
+                "Keychain access unavailable — the app may be missing entitlements or needs \
+                 to be granted access in System Settings. ({e})"
+            )
+        })
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    fn keychain_self_test() -> Option<String> {
+        None
+    }
+
+    /// The Setup tab's "Test keychain access" button.
+    pub fn test_keychain(&mut self) {
+        match Self::keychain_self_test() {
+            Some(problem) => self.add_notification(Level::Warning, &problem),
+            None => self.add_notification(Level::Info, "Keychain access works."),
+        };
+    }
+
+    /// The saved credentials, from the keyring or, when session caching is on, from memory
+    /// after the first successful read. The cached copy is wiped when dropped.
+    fn load_credentials(&mut self) -> Option<(Zeroizing<String>, Zeroizing<String>)> {
+        if !self.cache_credentials_in_memory {
+            self.cached_credentials = None;
+        }
+        if let Some(cached) = &self.cached_credentials {
+            return Some(cached.clone());
+        }
+        let service = keyring_service(&self.profile);
+        let username = Entry::new(&service, "username").get_password().ok()?;
+        let password = Entry::new(&service, "password").get_password().ok()?;
+        let credentials = (Zeroizing::new(username), Zeroizing::new(password));
+        if self.cache_credentials_in_memory {
+            self.cached_credentials = Some(credentials.clone());
+        }
+        Some(credentials)
+    }
+
+    /// The stored username for `profile`, whether usable credentials are saved, and whether
+    /// a stored value is empty. An empty value can only come from an earlier bad save; it is
+    /// treated as missing so the user is asked to re-enter it instead of Start failing with
+    /// no explanation.
+    fn read_saved_username(profile: &str) -> (String, bool, bool) {
+        let service = keyring_service(profile);
+        match (
+            Entry::new(&service, "username").get_password(),
+            Entry::new(&service, "password").get_password(),
+        ) {
+            (Ok(u), Ok(p)) if credentials_usable(&u, &p) => (u, true, false),
+            (Ok(_), Ok(_)) => (String::new(), false, true),
+            _ => (String::new(), false, false),
+        }
+    }
+
+    /// Switch credential profiles and show the new profile's saved username.
+    pub fn select_profile(&mut self, profile: String) {
+        if profile == self.profile {
+            return;
+        }
+        self.profile = profile;
+        self.password_input.clear();
+        self.editing_credentials = false;
+        self.cached_credentials = None;
+        let (username, saved, empty) = Self::read_saved_username(&self.profile);
+        self.username_input = username;
+        self.credentials_saved = saved;
+        if empty {
+            self.add_notification(
+                Level::Warning,
+                "The saved username or password is empty. Please enter and save your \
+                 credentials again.",
+            );
+        }
+    }
+
+    /// Leave the credential editor without saving, showing the saved username again.
+    pub fn cancel_credential_edit(&mut self) {
+        self.editing_credentials = false;
+        self.password_input.clear();
+        self.username_input = Self::read_saved_username(&self.profile).0;
+    }
+
+    /// Add a profile named by `new_profile_name` and select it.
+    pub fn add_profile(&mut self) {
+        let name = self.new_profile_name.trim().to_string();
+        if name.is_empty() || self.profiles.contains(&name) {
+            return;
+        }
+        self.profiles.push(name.clone());
+        self.new_profile_name.clear();
+        self.select_profile(name);
+    }
+
+    /// Remove the selected named profile and its saved credentials, then go back to the
+    /// default profile.
+    pub fn delete_profile(&mut self) {
+        if self.profile.is_empty() {
+            return;
+        }
+        self.remove_credentials();
+        let profile = self.profile.clone();
+        self.profiles.retain(|p| *p != profile);
+        self.select_profile(String::new());
+        self.add_notification(Level::Info, &format!("Profile {profile} deleted."));
+    }
+
+    /// Whether the Setup inputs can be saved: a username, and a password unless editing
+    /// keeps the stored one. Saving empty values would bring back the unusable state
+    /// `read_saved_username` warns about.
+    pub fn credential_inputs_complete(&self) -> bool {
+        let password = if self.editing_credentials && self.password_input.is_empty() {
+            "(kept)"
+        } else {
+            self.password_input.as_str()
+        };
+        credentials_usable(&self.username_input, password)
+    }
+
+    pub fn save_credentials(&mut self) {
+        let service = keyring_service(&self.profile);
+        let username_entry = Entry::new(&service, "username");
+        let password_entry = Entry::new(&service, "password");
+
+        // Editing saved credentials with the password left blank only changes the username.
+        let keep_password = self.editing_credentials && self.password_input.is_empty();
+        if !self.credential_inputs_complete() {
+            self.add_notification(
+                Level::Warning,
+                "Enter a username and password before saving.",
+            );
+            return;
+        }
+        let saved = username_entry.set_password(&self.username_input).is_ok()
+            && (keep_password || password_entry.set_password(&self.password_input).is_ok());
+        if saved {
+            self.credentials_saved = true;
+            self.editing_credentials = false;
+            self.password_input.clear();
+            self.cached_credentials = None; // read the new ones back on the next start
+            self.show_password = false;
+            self.add_notification(Level::Info, "Credentials saved.");
+        } else {
+            let problem = Self::keychain_self_test()
+                .unwrap_or_else(|| "Failed to save credentials.".to_string());
+            self.add_notification(Level::Error, &problem);
+        }
+    }
+
+    pub fn remove_credentials(&mut self) {
+        let service = keyring_service(&self.profile);
+        let username_entry = Entry::new(&service, "username");
+        let password_entry = Entry::new(&service, "password");
+
+        let _ = username_entry.delete_password();
+        let _ = password_entry.delete_password();
+        self.username_input.clear();
+        self.password_input.clear();
+        self.credentials_saved = false;
+        self.editing_credentials = false;
+        self.cached_credentials = None;
+        self.add_notification(Level::Info, "Credentials removed.");
+    }
+
+    /// Average time `update` took over the last `FRAME_TIME_SAMPLES` frames. It covers
+    /// building and laying out the UI, which is what grows with the log size.
+    pub fn average_frame_time(&self) -> Option<Duration> {
+        let count = u32::try_from(self.frame_times.len())
+            .ok()
+            .filter(|&n| n > 0)?;
+        Some(self.frame_times.iter().sum::<Duration>() / count)
+    }
+
+    /// Briefly show a small confirmation, such as "Copied line". It goes to the notification
+    /// stack as an info message, which hides itself, and stays out of the log.
+    pub fn show_toast(&mut self, text: &str) {
+        self.notifications.add(Level::Info, text, true);
+    }
+
+    /// Show a message in the notification stack and record it in the log. Returns its id.
+    pub fn add_notification(&mut self, level: Level, text: &str) -> u64 {
+        self.append_log(&format!("{text}\n"));
+        self.notifications.add(level, text, true)
+    }
+
+    pub fn append_log(&mut self, text: &str) {
+        if self.headless {
+            // Nothing renders the buffer, so print instead of growing it forever.
+            print!("{text}");
+        } else {
+            self.log_buffer.push_str(text, self.max_log_lines);
+        }
+        self.write_log_sinks(text);
+    }
+
+    /// Put a marker such as `══ [Reset clicked at 14:03:22] ══` in the log, so user actions
+    /// can be lined up with the terminal output around them.
+    pub fn mark_user_action(&mut self, action: &str) {
+        if self.mark_user_actions {
+            let time = chrono::Local::now().format("%H:%M:%S");
+            self.append_log(&format!(
+                "{ACTION_MARKER_PREFIX}{action} clicked at {time}] ══\n"
+            ));
+        }
+    }
+
+    /// Write the on-screen log to `path` as UTF-8, with or without its ANSI color codes.
+    pub fn save_log(&mut self, path: &Path) {
+        let text = self.log_buffer.text();
+        let text = if self.strip_ansi_in_saved_log {
+            ansi::strip(&text).into_owned()
+        } else {
+            text
+        };
+        match fs::write(path, text) {
+            Ok(()) => {
+                self.add_notification(Level::Info, &format!("Log saved to {}", path.display()));
+            }
+            Err(e) => {
+                self.add_notification(Level::Error, &format!("Failed to save log: {e}"));
+            }
+        }
+    }
+
+    /// Empty the on-screen log and what points into it. The log file is left alone.
+    pub fn clear_log(&mut self) {
+        self.log_buffer.clear();
+        self.recent_errors.clear();
+        self.log_jump_target = None;
+        self.append_log("Log cleared.\n");
+    }
+
+    /// Send text to the log file and the remote mirror without showing it on screen.
+    fn write_log_sinks(&mut self, text: &str) {
+        if let Some(file_log) = &mut self.file_log {
+            // Wrapping only applies to the file; the on-screen log keeps raw lines.
+            let wrap_width = self.log_file_wrap.then_some(self.log_file_wrap_width);
+            if let Err(e) = file_log.write(text, wrap_width) {
+                // Reporting through append_log would recurse, so just surface it in the UI.
+                self.file_log_error = Some(e.to_string());
+                self.file_log = None;
+            }
+        }
+        if let Some(mirror) = &self.log_mirror {
+            mirror.send(text);
+        }
+    }
+
+    /// Status endpoint paths from the settings box, ignoring blank lines.
+    pub fn status_paths(&self) -> Vec<String> {
+        self.status_paths_text
+            .lines()
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Where to poll for status: our own terminal on localhost while it runs, otherwise the
+    /// monitored terminal while monitoring-only mode (no credentials saved) is on.
+    fn status_target(&self) -> Option<(String, u16)> {
+        if self.process.is_some() {
+            Some(("127.0.0.1".to_string(), self.rest_port))
+        } else if self.monitoring && !self.credentials_saved {
+            Some((self.monitor_host.trim().to_string(), self.monitor_port))
+        } else {
+            None
+        }
+    }
+
+    /// Poll the status endpoint of the terminal we launched or monitor; stop and forget the
+    /// result when there is none.
+    fn sync_status_poller(&mut self) {
+        let target = self.status_target();
+        if target != self.status_poller_target {
+            // Started, stopped or pointed elsewhere: earlier results don't apply.
+            self.status_poller = None;
+            self.terminal_status = None;
+            self.feed_history.clear();
+            self.last_feed_counter = None;
+            self.was_connected = false;
+            self.status_poller_target = target.clone();
+        }
+        let Some((host, port)) = target else {
+            return;
+        };
+        if self.status_poller.is_none() {
+            let stats_path = Some(self.stats_path.trim().to_string()).filter(|p| !p.is_empty());
+            self.status_poller = Some(StatusPoller::start(
+                host,
+                port,
+                self.status_paths(),
+                stats_path,
+            ));
+        }
+        if let Some(report) = self.status_poller.as_ref().and_then(StatusPoller::latest) {
+            if let Some(Ok(stats)) = &report.stats {
+                self.record_feed_stats(*stats);
+            }
+            let connected = report.is_connected();
+            if self.was_connected && !connected {
+                self.append_log("Terminal lost its connection.\n");
+                self.connection_notification = Some(self.notifications.add(
+                    Level::Error,
+                    "Terminal lost its connection.",
+                    false,
+                ));
+                self.raise_alert();
+            }
+            if connected {
+                if let Some(id) = self.connection_notification.take() {
+                    self.notifications.dismiss(id);
+                }
+            }
+            self.was_connected = connected;
+            self.terminal_status = Some(report);
+        }
+    }
+
+    /// Play the alert sound, if enabled; repeated alerts are debounced.
+    fn raise_alert(&mut self) {
+        if self.alert_sound_enabled {
+            self.alert_sound.trigger(self.alert_volume);
+        }
+    }
+
+    /// Turn the cumulative message counter into a rate and keep a bounded history for plots.
+    fn record_feed_stats(&mut self, stats: FeedStats) {
+        let now = Instant::now();
+        let message_rate = match (stats.messages_received, self.last_feed_counter) {
+            (Some(count), Some((previous, at))) if count >= previous => {
+                Some((count - previous) as f64 / now.duration_since(at).as_secs_f64().max(1e-3))
+            }
+            _ => None, // first sample, or the counter reset with a restart
+        };
+        self.last_feed_counter = stats.messages_received.map(|count| (count, now));
+        if self.feed_history.len() == FEED_HISTORY_LEN {
+            self.feed_history.pop_front();
+        }
+        self.feed_history.push_back(FeedSample {
+            message_rate,
+            queue_depth: stats.queue_depth.map(|depth| depth as f64),
+        });
+    }
+
+    /// When enabled, check for a newer release at most once a day, on a background thread.
+    fn sync_update_check(&mut self) {
+        if let Some(result) = self.update_check.as_ref().and_then(|rx| rx.try_recv().ok()) {
+            self.update_check = None;
+            match result {
+                Ok(release) if updates::is_newer(&release.version, env!("CARGO_PKG_VERSION")) => {
+                    self.available_update = Some((release.version, release.notes_url));
+                }
+                Ok(_) => self.available_update = None,
+                Err(e) => self.append_log(&format!("Update check failed: {e}\n")),
+            }
+        }
+        if !self.check_for_updates || self.update_check.is_some() {
+            return;
+        }
+        let now = Local::now().timestamp();
+        if self
+            .last_update_check
+            .is_some_and(|last| now - last < updates::CHECK_INTERVAL_SECS)
+        {
+            return;
+        }
+        self.last_update_check = Some(now);
+        let (tx, rx) = channel();
+        thread::spawn(move || {
+            let _ = tx.send(updates::fetch_latest());
+        });
+        self.update_check = Some(rx);
+    }
+
+    /// Open or close the daily log file to match the `log_to_file` setting.
+    fn sync_file_log(&mut self) {
+        if self.log_to_file == self.file_log_applied {
+            return;
+        }
+        self.file_log_applied = self.log_to_file;
+        self.file_log = None;
+        self.file_log_error = None;
+        if self.log_to_file {
+            let opened = Self::app_data_dir()
+                .ok_or_else(|| std::io::Error::other("no app config directory"))
+                .and_then(|dir| FileLog::open(dir.join("logs")));
+            match opened {
+                Ok(file_log) => {
+                    let path = file_log.current_path();
+                    self.file_log = Some(file_log);
+                    self.append_log(&format!("Writing log to {}\n", path.display()));
+                }
+                Err(e) => self.file_log_error = Some(e.to_string()),
+            }
+        }
+    }
+
+    /// Truncate today's log file to reclaim space, leaving the on-screen log alone. Works
+    /// whether or not the app currently has the file open.
+    pub fn truncate_log_file(&mut self) {
+        let result = match &mut self.file_log {
+            Some(file_log) => file_log.truncate(),
+            None => Self::app_data_dir()
+                .map(|dir| FileLog::path_for(&dir.join("logs"), Local::now().date_naive()))
+                .filter(|path| path.is_file())
+                .ok_or_else(|| std::io::Error::other("there is no log file for today"))
+                .and_then(|path| file_log::truncate_with_marker(&path).map(|()| path)),
+        };
+        match result {
+            Ok(path) => self.append_log(&format!("Truncated log file {}\n", path.display())),
+            Err(e) => {
+                self.add_notification(Level::Error, &format!("Failed to truncate log file: {e}"));
+            }
+        }
+    }
+
+    /// Start, restart or stop the TCP log mirror to match the current settings.
+    fn sync_log_mirror(&mut self) {
+        let desired = self
+            .log_mirror_enabled
+            .then_some((self.log_mirror_port, self.log_mirror_all_interfaces));
+        if desired == self.log_mirror_applied {
+            return;
+        }
+        self.log_mirror_applied = desired;
+        self.log_mirror = None; // release the old port before rebinding
+        self.log_mirror_error = None;
+        if let Some(id) = self.log_mirror_notification.take() {
+            self.notifications.dismiss(id);
+        }
+        if let Some((port, all_interfaces)) = desired {
+            match LogMirror::start(port, all_interfaces) {
+                Ok(mirror) => {
+                    let addr = mirror.addr;
+                    if mirror.is_public() {
+                        let text = format!(
+                            "The log mirror is listening on all interfaces ({addr}): anyone who \
+                             can reach this machine can read the log, without a password."
+                        );
+                        self.append_log(&format!("{text}\n"));
+                        self.log_mirror_notification =
+                            Some(self.notifications.add(Level::Warning, &text, false));
+                    }
+                    self.log_mirror = Some(mirror);
+                    self.append_log(&format!("Log mirror listening on {addr}\n"));
+                }
+                Err(e) => {
+                    self.log_mirror_error = Some(e.to_string());
+                    self.add_notification(
+                        Level::Error,
+                        &format!("Failed to start log mirror on port {port}: {e}"),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Take one line from the terminal's output: mask the credentials before any detector,
+    /// the screen or a log sink sees it, then record it.
+    fn ingest_terminal_line(&mut self, mut line: TerminalLine, show: bool) {
+        self.redactor.redact(&mut line.text);
+        self.handle_terminal_line(&line, show);
+    }
+
+    /// Record one line of terminal output and run the log detectors over it. With `show`
+    /// false (the flood guard is sampling) the line stays off the screen, but the detectors
+    /// still see it and it still reaches the log file and the mirror.
+    pub fn handle_terminal_line(&mut self, terminal_line: &TerminalLine, show: bool) {
+        let line = terminal_line.text.as_str();
+        let severity = severity::classify(line);
+        if severity == Severity::Error || severity::is_auth_failure(line) {
+            self.raise_alert();
+        }
+        if severity::is_auth_failure(line) {
+            self.notifications.add(
+                Level::Error,
+                "Login failed — check the saved credentials.",
+                true,
+            );
+        }
+        if severity != Severity::Info {
+            if self.recent_errors.len() == MAX_RECENT_ERRORS {
+                self.recent_errors.pop_front();
+            }
+            self.recent_errors.push_back(RecentError {
+                severity,
+                text: line.to_string(),
+                received_at: terminal_line.received_at,
+                log_offset: self.log_buffer.end_offset(),
+            });
+        }
+        if !show {
+            self.skip_terminal_line(terminal_line);
+        } else {
+            if self.show_timestamps {
+                let stamp = self.format_timestamp(terminal_line.received_at);
+                self.append_log(&format!("[{stamp}] "));
+            }
+            self.append_log(line);
+            self.append_log("\n");
+        }
+        self.detect_config_file_path_in_line(line);
+        if self.port_holder.is_none()
+            && (line.contains("Address already in use") || line.contains("BindException"))
+        {
+            self.diagnose_port_conflict();
+        }
+        if let Some((required, running)) = java::parse_class_version_error(line) {
+            self.report_java_too_old(required, running);
+        }
+    }
+
+    /// Everything that keeps the terminal supervised, independent of the UI: drain its
+    /// output, keep the log sinks and pollers in step with the settings, and notice when the
+    /// process exits. Called every frame by the GUI and in a loop by the headless watchdog.
+    /// Returns the exit status if the process exited during this call.
+    pub fn supervise(&mut self) -> Option<ExitStatus> {
+        let mut new_lines: Vec<TerminalLine> = if let Some(rx) = &self.log_receiver {
+            rx.try_iter().collect()
+        } else {
+            Vec::new()
+        };
+        // Tailed lines can only be stamped when we read them, once per poll interval.
+        if let Some(tail) = &mut self.output_tail {
+            if let Ok(lines) = tail.poll(false) {
+                new_lines.extend(lines.into_iter().map(TerminalLine::now));
+            }
+        }
+        let new_lines = if self.order_output_by_time && self.process.is_some() {
+            self.reorder_output(new_lines)
+        } else {
+            // Release anything still held from before the option was turned off or the
+            // process exited.
+            let mut held = std::mem::take(&mut self.reorder_buffer);
+            held.extend(new_lines);
+            held
+        };
+        self.flood_guard
+            .record(new_lines.len(), self.flood_threshold);
+        for line in new_lines {
+            let show = self.flood_guard.should_show();
+            self.ingest_terminal_line(line, show);
+        }
+
+        self.sync_file_log();
+        self.sync_log_mirror();
+        self.sync_status_poller();
+        self.run_state = self
+            .run_state
+            .next(self.process.is_some(), self.terminal_status.as_ref());
+        self.watch_config_file();
+        self.poll_java_check();
+        self.poll_jar_test();
+        self.poll_login_test();
+        self.poll_pending_auto_load();
+        self.sync_update_check();
+        self.poll_graceful_stop();
+
+        let status = self.process.as_mut()?.try_wait().ok()??;
+        self.process = None;
+        self.stop_terminal_guard();
+        self.started_at = None;
+        self.process_pid = None;
+        self.last_exit_code = status.code();
+        self.stdin = None;
+        self.finish_readers();
+        self.finish_output_tail();
+        // Nothing more can echo the credentials, so don't keep a copy of them around.
+        self.redactor = Redactor::default();
+        self.append_log(&format!("Terminal process exited ({status}).\n"));
+        // Stop sets stop_requested and Reset never gets here, so only surprises notify.
+        if self.notify_on_exit && !self.stop_requested {
+            desktop_notification::show(
+                "ThetaData Terminal exited",
+                &format!("The terminal stopped unexpectedly ({status})."),
+            );
+        }
+        if self.stop_deadline.take().is_some() {
+            self.append_log("Terminal stopped gracefully.\n");
+        } else if !status.success() {
+            self.raise_alert();
+        }
+        Some(status)
+    }
+
+    /// Compact status for the window title and tray tooltip; see `format_status_summary`.
+    pub fn status_summary(&self) -> String {
+        let connection = self
+            .terminal_status
+            .as_ref()
+            .map(|report| match report.path {
+                Some(_) => report.detail.as_str(),
+                None => "Disconnected",
+            });
+        format_status_summary(
+            self.process.is_some(),
+            connection,
+            self.running_profile.as_deref(),
+            self.started_at.map(|started| started.elapsed()),
+        )
+    }
+
+    /// Render a timestamp with the configured pattern, falling back to the default pattern
+    /// when the configured one doesn't parse.
+    pub fn format_timestamp(&self, at: DateTime<Local>) -> String {
+        let pattern = if timestamp_format_is_valid(&self.log_timestamp_format) {
+            self.log_timestamp_format.as_str()
+        } else {
+            DEFAULT_TIMESTAMP_FORMAT
+        };
+        if self.log_timestamp_utc {
+            at.with_timezone(&Utc).format(pattern).to_string()
+        } else {
+            at.format(pattern).to_string()
+        }
+    }
+
+    /// Write a line the flood guard keeps off the screen to the log file and the mirror, so
+    /// nothing is lost there.
+    fn skip_terminal_line(&mut self, terminal_line: &TerminalLine) {
+        let text = if self.show_timestamps {
+            let stamp = self.format_timestamp(terminal_line.received_at);
+            format!("[{stamp}] {}\n", terminal_line.text)
+        } else {
+            format!("{}\n", terminal_line.text)
+        };
+        self.write_log_sinks(&text);
+    }
+
+    /// stdout and stderr are read on separate threads, so their lines reach the channel in
+    /// whatever order the threads were scheduled. Hold lines for a short window and release
+    /// them sorted by the time they were read, trading that much latency for ordering.
+    fn reorder_output(&mut self, lines: Vec<TerminalLine>) -> Vec<TerminalLine> {
+        self.reorder_buffer.extend(lines);
+        // Stable, so lines from one stream with equal stamps keep their order.
+        self.reorder_buffer.sort_by_key(|line| line.received_at);
+        let cutoff = Local::now() - chrono::Duration::milliseconds(OUTPUT_REORDER_WINDOW_MS);
+        let ready = self
+            .reorder_buffer
+            .iter()
+            .take_while(|line| line.received_at <= cutoff)
+            .count();
+        self.reorder_buffer.drain(..ready).collect()
+    }
+
+    /// Explain an `UnsupportedClassVersionError` and remember the requirement for this jar.
+    fn report_java_too_old(&mut self, required: u32, running: Option<u32>) {
+        let detected = running.map_or_else(|| "an older version".to_string(), |v| v.to_string());
+        let message = format!(
+            "ThetaTerminal needs a newer Java version than the one launched \
+             (detected {detected}, requires {required}). Set a different Java path."
+        );
+        self.add_notification(Level::Error, &message);
+        self.java_requirement = Some(JavaRequirement {
+            jar_path: self.jar_path.clone(),
+            major: required,
+        });
+    }
+
+    /// Pre-launch check against a requirement learned from an earlier failed launch.
+    /// Returns false (and shows the banner) when the installed Java is known to be too old.
+    fn java_meets_known_requirement(&mut self, java_version: &str) -> bool {
+        let Some(requirement) = self
+            .java_requirement
+            .as_ref()
+            .filter(|r| r.jar_path == self.jar_path)
+        else {
+            return true;
+        };
+        let required = requirement.major;
+        match java::parse_java_major(java_version) {
+            Some(major) if major < required => {
+                self.report_java_too_old(required, Some(major));
+                false
+            }
+            _ => true,
+        }
+    }
+
+    /// Identify which process is holding the REST port and report it.
+    pub fn diagnose_port_conflict(&mut self) {
+        let port = self.rest_port;
+        if ports::port_available(port) {
+            self.port_holder = None;
+            self.append_log(&format!("Port {port} is free.\n"));
+            return;
+        }
+        match ports::find_port_holder(port) {
+            Some(holder) => {
+                let ours = self.process.as_ref().map(|c| c.id()) == Some(holder.pid);
+                let note = if ours {
+                    " — the terminal started by this app"
+                } else {
+                    ""
+                };
+                self.add_notification(Level::Warning, &format!("{}{note}.", holder.describe()));
+                self.port_holder = Some(holder);
+            }
+            None => {
+                self.add_notification(
+                    Level::Warning,
+                    &format!(
+                        "Port {port} is in use, but the owning process could not be identified."
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Kill the process found by `diagnose_port_conflict`.
+    pub fn kill_port_holder(&mut self) {
+        self.confirm_kill_port_holder = false;
+        if let Some(holder) = self.port_holder.take() {
+            match ports::kill_process(holder.pid) {
+                Ok(true) => {
+                    self.add_notification(
+                        Level::Info,
+                        &format!("Killed {} (PID {}).", holder.name, holder.pid),
+                    );
+                }
+                Ok(false) => {
+                    self.add_notification(
+                        Level::Warning,
+                        &format!("Could not kill {} (PID {}).", holder.name, holder.pid),
+                    );
+                }
+                Err(e) => {
+                    self.add_notification(
+                        Level::Error,
+                        &format!("Failed to kill PID {}: {e}", holder.pid),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Detect and capture a config file path from a log line.
+    pub fn detect_config_file_path_in_line(&mut self, line: &str) {
+        let prefix = "Using ";
+        let suffix = " as the config file";
+        if let (Some(start_idx), Some(end_idx)) = (line.find(prefix), line.find(suffix)) {
+            let path_start = start_idx + prefix.len();
+            if path_start < end_idx {
+                let raw_path = &line[path_start..end_idx].trim();
+                self.last_detected_config_path = Some(raw_path.to_string());
+                self.append_log(&format!(
+                    "Detected config file path from terminal: {raw_path}\n"
+                ));
+                if self.auto_load_detected_config && *raw_path != self.thetadata_config_path {
+                    // The terminal may still be writing the file; load it once it settles.
+                    self.pending_auto_load = Some(PendingAutoLoad {
+                        path: raw_path.to_string(),
+                        due: Instant::now() + Duration::from_millis(self.auto_load_delay_ms),
+                        last_size: None,
+                        checks: 0,
+                    });
+                }
+            }
+        }
+    }
+
+    /// Auto-load a detected config once its size reads the same twice in a row, so a file
+    /// the terminal is still writing is never shown half-written.
+    fn poll_pending_auto_load(&mut self) {
+        let Some(pending) = &mut self.pending_auto_load else {
+            return;
+        };
+        if Instant::now() < pending.due {
+            return;
+        }
+        let size = fs::metadata(&pending.path).ok().map(|m| m.len());
+        let settled = size.is_some() && size == pending.last_size;
+        let changed = !settled && pending.last_size.is_some();
+        pending.checks += 1;
+        pending.last_size = size;
+        pending.due = Instant::now() + AUTO_LOAD_STABILITY_GAP;
+        let give_up = pending.checks > AUTO_LOAD_MAX_CHECKS;
+        let path = pending.path.clone();
+
+        if settled {
+            self.pending_auto_load = None;
+            self.auto_load_config(&path);
+        } else if give_up {
+            self.pending_auto_load = None;
+            self.add_notification(
+                Level::Warning,
+                &format!(
+                    "Gave up auto-loading {path}: the file kept changing. Use Refresh to load it."
+                ),
+            );
+        } else if changed {
+            self.append_log(&format!(
+                "Detected config {path} is still changing; waiting before loading it.\n"
+            ));
+        }
+    }
+
+    /// Load a config reported by the terminal, unless that would discard unsaved edits.
+    fn auto_load_config(&mut self, path: &str) {
+        if self.config_is_dirty() {
+            self.add_notification(
+                Level::Warning,
+                "Not auto-loading the detected config: you have unsaved edits.",
+            );
+            return;
+        }
+        match self.load_config_file(path) {
+            Ok(()) => {
+                self.add_notification(
+                    Level::Info,
+                    &format!("Auto-loaded config detected from terminal: {path}"),
+                );
+            }
+            Err(e) => {
+                self.add_notification(
+                    Level::Error,
+                    &format!("Failed to auto-load detected config: {e}"),
+                );
+            }
+        }
+    }
+
+    /// Load `path` into the editor and make it the active config file.
+    pub fn load_config_file(&mut self, path: &str) -> std::io::Result<()> {
+        let (text, encoding) = self.read_config_with_retry(path)?;
+        self.thetadata_config_path = path.to_string();
+        self.config_encoding = encoding;
+        self.save_config_as_utf8 = false;
+        self.config_saved_text = text.clone();
+        self.thetadata_config_text = text;
+        self.reset_config_history();
+        self.structured_edits.clear();
+        self.pending_structured_apply = None;
+        self.config_on_disk = true;
+        self.remember_config_mtime();
+        self.remember_recent_config(path);
+        Ok(())
+    }
+
+    /// Load `path` from `source` (e.g. "terminal detection"), first asking whether to discard
+    /// unsaved edits if there are any.
+    pub fn request_config_load(&mut self, path: String, source: &'static str) {
+        if self.config_is_dirty() {
+            self.pending_config_load = Some((path, source));
+        } else {
+            self.load_config_from(&path, source);
+        }
+    }
+
+    /// Answer the discard prompt raised by `request_config_load`.
+    pub fn resolve_pending_config_load(&mut self, save_first: bool) {
+        let Some((path, source)) = self.pending_config_load.take() else {
+            return;
+        };
+        if save_first && !self.save_current_config_file() {
+            return;
+        }
+        self.load_config_from(&path, source);
+    }
+
+    fn load_config_from(&mut self, path: &str, source: &str) {
+        match self.load_config_file(path) {
+            Ok(()) => self.append_log(&format!("Config file loaded from {source}.\n")),
+            Err(e) => {
+                self.add_notification(Level::Error, &format!("Failed to load config file: {e}"));
+            }
+        }
+    }
+
+    /// Write the editor contents to `path` and make it the active config file.
+    /// The previously open file is left untouched.
+    pub fn save_config_as(&mut self, path: &str) {
+        let encoding = self.save_encoding();
+        match Self::write_thetadata_config_file(path, &self.thetadata_config_text, encoding) {
+            Ok(()) => {
+                self.note_saved_encoding(path, encoding);
+                self.thetadata_config_path = path.to_string();
+                self.config_saved_text = self.thetadata_config_text.clone();
+                self.config_on_disk = true;
+                self.remember_config_mtime();
+                self.remember_recent_config(path);
+                self.add_notification(Level::Info, &format!("Config saved as {path}"));
+                self.record_save_summary(path);
+            }
+            Err(e) => {
+                self.add_notification(
+                    Level::Error,
+                    &format!("Failed to save config as {path}: {e}"),
+                );
+            }
+        }
+    }
+
+    fn record_save_summary(&mut self, path: &str) {
+        let text = &self.thetadata_config_text;
+        let duplicates = properties::duplicate_keys(&properties::parse_entries(text));
+        let mut warnings = Vec::new();
+        if !duplicates.is_empty() {
+            let keys: Vec<&str> = duplicates.iter().map(|(key, _)| key.as_str()).collect();
+            warnings.push(format!(
+                "{} duplicate key(s) left in place: {}",
+                duplicates.len(),
+                keys.join(", ")
+            ));
+        }
+        self.save_summary = Some(SaveSummary {
+            path: path.to_string(),
+            lines: text.lines().count(),
+            bytes: fs::metadata(path).map(|m| m.len()).unwrap_or(0),
+            latest_backup: backup::list_backups(Path::new(path)).pop(),
+            warnings,
+            saved_at: Instant::now(),
+        });
+    }
+
+    /// The post-save summary, until it is dismissed or `SAVE_SUMMARY_DURATION` passes.
+    pub fn visible_save_summary(&mut self) -> Option<&SaveSummary> {
+        if self
+            .save_summary
+            .as_ref()
+            .is_some_and(|summary| summary.saved_at.elapsed() >= SAVE_SUMMARY_DURATION)
+        {
+            self.save_summary = None;
+        }
+        self.save_summary.as_ref()
+    }
+
+    fn remember_recent_config(&mut self, path: &str) {
+        self.recent_config_paths.retain(|p| p != path);
+        self.recent_config_paths.insert(0, path.to_string());
+        self.recent_config_paths.truncate(MAX_RECENT_CONFIGS);
+    }
+
+    /// `HTTP_PORT` from the config being edited. The text is only parsed again once it has
+    /// changed, not on every frame that shows the port.
+    pub fn config_http_port(&mut self) -> Option<u16> {
+        match &self.config_port_parsed {
+            Some((text, port)) if *text == self.thetadata_config_text => *port,
+            _ => {
+                let entries = properties::parse_entries(&self.thetadata_config_text);
+                let port = properties::http_port(&entries);
+                self.config_port_parsed = Some((self.thetadata_config_text.clone(), port));
+                port
+            }
+        }
+    }
+
+    /// The config path the running terminal reported, if it differs from the one being edited.
+    /// The comparison resolves both paths on disk, so it is only redone when either changes.
+    pub fn running_config_mismatch(&mut self) -> Option<String> {
+        self.process.as_ref()?;
+        let detected = self.last_detected_config_path.as_ref()?;
+        if self.thetadata_config_path.is_empty() {
+            return None;
+        }
+        let cached = self
+            .config_paths_compared
+            .as_ref()
+            .filter(|(d, e, _)| d == detected && *e == self.thetadata_config_path)
+            .map(|(_, _, same)| *same);
+        let same = cached.unwrap_or_else(|| {
+            let canonical = |p: &str| fs::canonicalize(p).unwrap_or_else(|_| PathBuf::from(p));
+            canonical(detected) == canonical(&self.thetadata_config_path)
+        });
+        let detected = detected.clone();
+        if cached.is_none() {
+            self.config_paths_compared =
+                Some((detected.clone(), self.thetadata_config_path.clone(), same));
+        }
+        (!same).then_some(detected)
+    }
+
+    /// Record a config edit made since the last call as an undo step. Small changes in quick
+    /// succession (typing) extend the current step; anything larger, like a paste or a
+    /// replace, is a step of its own.
+    fn track_config_edits(&mut self) {
+        if self.thetadata_config_text == self.config_history_text {
+            return;
+        }
+        let small = self
+            .thetadata_config_text
+            .len()
+            .abs_diff(self.config_history_text.len())
+            <= 4; // one character, whatever its encoded length
+        let typing = small
+            && self
+                .config_last_edit
+                .is_some_and(|at| at.elapsed() < CONFIG_UNDO_COALESCE);
+        let previous = std::mem::replace(
+            &mut self.config_history_text,
+            self.thetadata_config_text.clone(),
+        );
+        if !typing {
+            if self.config_undo_stack.len() == CONFIG_UNDO_LIMIT {
+                self.config_undo_stack.pop_front();
+            }
+            self.config_undo_stack.push_back(previous);
+        }
+        self.config_redo_stack.clear();
+        self.config_last_edit = small.then(Instant::now);
+    }
+
+    pub fn undo_config_edit(&mut self) {
+        self.track_config_edits();
+        if let Some(previous) = self.config_undo_stack.pop_back() {
+            let current = std::mem::replace(&mut self.thetadata_config_text, previous);
+            self.config_redo_stack.push(current);
+            self.config_history_text = self.thetadata_config_text.clone();
+            self.config_last_edit = None;
+        }
+    }
+
+    pub fn redo_config_edit(&mut self) {
+        self.track_config_edits();
+        if let Some(next) = self.config_redo_stack.pop() {
+            let current = std::mem::replace(&mut self.thetadata_config_text, next);
+            self.config_undo_stack.push_back(current);
+            self.config_history_text = self.thetadata_config_text.clone();
+            self.config_last_edit = None;
+        }
+    }
+
+    /// Forget the edit history, e.g. when a different file is loaded.
+    fn reset_config_history(&mut self) {
+        self.config_undo_stack.clear();
+        self.config_redo_stack.clear();
+        self.config_history_text = self.thetadata_config_text.clone();
+        self.config_last_edit = None;
+        self.config_selection = None;
+    }
+
+    /// Whether the editor holds changes that haven't been written to disk.
+    pub fn config_is_dirty(&self) -> bool {
+        self.thetadata_config_text != self.config_saved_text
+    }
+
+    /// Read the ThetaData config file.
+    /// If the file isn’t valid UTF‑8, decode it as Windows‑1252.
+    pub fn read_thetadata_config_file(path: &str) -> std::io::Result<(String, ConfigEncoding)> {
+        let bytes = fs::read(path)?;
+        match String::from_utf8(bytes.clone()) {
+            Ok(s) => Ok((s, ConfigEncoding::Utf8)),
+            Err(_) => {
+                let (cow, _, _) = WINDOWS_1252.decode(&bytes);
+                Ok((cow.into_owned(), ConfigEncoding::Windows1252))
+            }
+        }
+    }
+
+    /// `read_thetadata_config_file`, retried with backoff while the error looks transient
+    /// (a network drive hiccup, a virus scanner holding the file). Logs each retry.
+    pub fn read_config_with_retry(
+        &mut self,
+        path: &str,
+    ) -> std::io::Result<(String, ConfigEncoding)> {
+        let mut backoff = CONFIG_READ_FIRST_BACKOFF;
+        let mut attempt = 0;
+        loop {
+            match Self::read_thetadata_config_file(path) {
+                Err(e) if attempt < CONFIG_READ_RETRIES && is_transient_io_error(&e) => {
+                    attempt += 1;
+                    self.append_log(&format!(
+                        "Reading {path} failed ({e}); retrying in {} ms ({attempt} of \
+                         {CONFIG_READ_RETRIES}).\n",
+                        backoff.as_millis()
+                    ));
+                    thread::sleep(backoff);
+                    backoff *= 2;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Write the ThetaData config file in the given encoding. Text that Windows‑1252 can't
+    /// represent is refused rather than written with substitutes.
+    pub fn write_thetadata_config_file(
+        path: &str,
+        contents: &str,
+        encoding: ConfigEncoding,
+    ) -> std::io::Result<()> {
+        let bytes = match encoding {
+            ConfigEncoding::Utf8 => std::borrow::Cow::Borrowed(contents.as_bytes()),
+            ConfigEncoding::Windows1252 => {
+                let (bytes, _, unmappable) = WINDOWS_1252.encode(contents);
+                if unmappable {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        "the text has characters Windows-1252 can't store; use Save as UTF-8",
+                    ));
+                }
+                bytes
+            }
+        };
+        write_atomically(Path::new(path), &bytes)
+    }
+
+    /// Encoding the next save writes: the file's own, unless converting to UTF-8.
+    fn save_encoding(&self) -> ConfigEncoding {
+        if self.save_config_as_utf8 {
+            ConfigEncoding::Utf8
+        } else {
+            self.config_encoding
+        }
+    }
+
+    /// After a successful save, log a conversion if one happened and adopt the new encoding.
+    fn note_saved_encoding(&mut self, path: &str, encoding: ConfigEncoding) {
+        if encoding != self.config_encoding {
+            self.add_notification(
+                Level::Info,
+                &format!("Converted {path} from Windows-1252 to UTF-8."),
+            );
+        }
+        self.config_encoding = encoding;
+        self.save_config_as_utf8 = false;
+    }
+
+    /// Stage a structured edit, dropping it again if the value is back to the original.
+    pub fn stage_structured_edit(&mut self, entry: &ConfigEntry, value: String) {
+        if value == entry.value {
+            self.structured_edits.remove(&entry.line);
+        } else {
+            self.structured_edits.insert(
+                entry.line,
+                ConfigChange {
+                    line: entry.line,
+                    key: entry.key.clone(),
+                    old_value: entry.value.clone(),
+                    new_value: value,
+                },
+            );
+        }
+    }
+
+    /// Stage `MDDS_REGION`/`FPSS_REGION` edits pointing at the given region's host lists.
+    pub fn stage_region_preset(&mut self, entries: &[ConfigEntry], region: &str) {
+        for (key, value) in [
+            ("MDDS_REGION", format!("MDDS_{region}_HOSTS")),
+            ("FPSS_REGION", format!("FPSS_{region}_HOSTS")),
+        ] {
+            if let Some(entry) = entries.iter().find(|e| e.key == key) {
+                self.stage_structured_edit(entry, value);
+            }
+        }
+    }
+
+    /// Apply the staged edits, asking for confirmation first when more than one key changes.
+    pub fn request_structured_apply(&mut self) {
+        let changes: Vec<ConfigChange> = self.structured_edits.values().cloned().collect();
+        match changes.len() {
+            0 => {}
+            1 => self.apply_structured_edits(&changes),
+            _ => self.pending_structured_apply = Some(changes),
+        }
+    }
+
+    /// Write staged edits into the config text, skipping any whose line no longer holds that key.
+    pub fn apply_structured_edits(&mut self, changes: &[ConfigChange]) {
+        let entries = properties::parse_entries(&self.thetadata_config_text);
+        for change in changes {
+            if entries
+                .iter()
+                .any(|e| e.line == change.line && e.key == change.key)
+            {
+                self.thetadata_config_text =
+                    set_value(&self.thetadata_config_text, change.line, &change.new_value);
+            } else {
+                self.append_log(&format!(
+                    "Skipped stale edit for {} (line moved).\n",
+                    change.key
+                ));
+            }
+        }
+        self.structured_edits.clear();
+        self.pending_structured_apply = None;
+        self.append_log(&format!(
+            "Applied {} structured config change(s). Click 'Save' to persist.\n",
+            changes.len()
+        ));
+    }
+
+    /// Remove the duplicate key lines the policy says lose, keeping comments in place.
+    pub fn remove_duplicate_keys(&mut self) {
+        let keep_last = match self.duplicate_key_policy {
+            DuplicateKeyPolicy::Warn => return,
+            DuplicateKeyPolicy::KeepFirst => false,
+            DuplicateKeyPolicy::KeepLast => true,
+        };
+        let entries = properties::parse_entries(&self.thetadata_config_text);
+        let losing =
+            properties::losing_duplicate_lines(&properties::duplicate_keys(&entries), keep_last);
+        if losing.is_empty() {
+            return;
+        }
+        self.thetadata_config_text = properties::remove_lines(&self.thetadata_config_text, &losing);
+        // Line numbers of staged structured edits no longer match.
+        self.structured_edits.clear();
+        self.pending_structured_apply = None;
+        self.append_log(&format!(
+            "Removed {} duplicate config line(s). Click 'Save' to persist.\n",
+            losing.len()
+        ));
+    }
+
+    /// Comment out the lines the config editor's selection touches, or uncomment them if
+    /// they all already are.
+    pub fn toggle_comment_selection(&mut self) {
+        let text = &self.thetadata_config_text;
+        let Some(selection) = self.config_selection.clone() else {
+            return;
+        };
+        if selection.end > text.len()
+            || !text.is_char_boundary(selection.start)
+            || !text.is_char_boundary(selection.end)
+        {
+            return;
+        }
+        let first = text[..selection.start].matches('\n').count();
+        // A selection ending at the start of a line doesn't include that line.
+        let mut last = text[..selection.end].matches('\n').count();
+        if last > first && text[..selection.end].ends_with('\n') {
+            last -= 1;
+        }
+        self.thetadata_config_text = properties::toggle_comments(text, first..=last);
+        // Keep the toggled lines selected so another toggle undoes this one.
+        let line_starts: Vec<usize> = std::iter::once(0)
+            .chain(
+                self.thetadata_config_text
+                    .match_indices('\n')
+                    .map(|(i, _)| i + 1),
+            )
+            .collect();
+        let end = line_starts
+            .get(last + 1)
+            .map_or(self.thetadata_config_text.len(), |next| next - 1);
+        self.config_selection = Some(line_starts[first]..end);
+        // Staged structured edits may point at lines that are now comments.
+        self.structured_edits.clear();
+        self.pending_structured_apply = None;
+    }
+
+    /// Remember the window's size and position for the next launch. On the first frame,
+    /// move a restored window back into view if it opened on a different monitor than it
+    /// was saved on, e.g. because that monitor was unplugged.
+    fn track_window_geometry(&mut self, frame: &mut eframe::Frame) {
+        let info = frame.info().window_info.clone();
+        if !self.window_position_checked {
+            self.window_position_checked = true;
+            if let Some(saved) = self.window_geometry {
+                if saved.position.is_some() && saved.monitor_size.is_some() {
+                    let current = info.monitor_size.map(|m| (m.x, m.y));
+                    if current != saved.monitor_size {
+                        let monitor = info.monitor_size.unwrap_or(info.size);
+                        let centered = ((monitor - info.size) / 2.0).max(Vec2::ZERO);
+                        frame.set_window_pos(centered.to_pos2());
+                        self.add_notification(
+                            Level::Info,
+                            "The window opened on a different monitor than it was saved on; \
+                             centering it.",
+                        );
+                        return;
+                    }
+                }
+            }
+        }
+        // A minimized window reports a parking position far off-screen on Windows.
+        if info.minimized || info.fullscreen {
+            return;
+        }
+        self.window_geometry = Some(WindowGeometry {
+            size: (info.size.x, info.size.y),
+            position: info.position.map(|p| (p.x, p.y)),
+            monitor_size: info.monitor_size.map(|m| (m.x, m.y)),
+        });
+    }
+
+    /// Ctrl+1/2/3 switch tabs; Ctrl+S/K/R start, stop and reset the terminal like their
+    /// buttons. Ignored while a text field has focus, so typing never triggers them.
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+        let pressed = |key| ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, key));
+        for (key, tab) in [
+            (egui::Key::Num1, Tab::Setup),
+            (egui::Key::Num2, Tab::Terminal),
+            (egui::Key::Num3, Tab::Config),
+        ] {
+            if pressed(key) {
+                self.selected_tab = tab;
+            }
+        }
+        if pressed(egui::Key::S) && self.credentials_saved {
+            self.mark_user_action("Start");
+            self.request_launch(LaunchAction::Start);
+        }
+        if pressed(egui::Key::K) {
+            self.mark_user_action("Stop");
+            self.stop_terminal();
+        }
+        if pressed(egui::Key::R) && self.credentials_saved {
+            self.mark_user_action("Reset");
+            self.request_reset();
+        }
+    }
+
+    /// Keep the tray icon in line with the setting and act on its menu. Quit there is the
+    /// only way out while the window hides to the tray.
+    fn handle_tray(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        if !self.minimize_to_tray {
+            self.tray = None;
+            return;
+        }
+        if self.tray.is_none() && !self.tray_unavailable {
+            match Tray::new(ctx) {
+                Ok(tray) => {
+                    tray.update(&self.status_summary(), self.process.is_some());
+                    self.tray = Some(tray);
+                }
+                Err(e) => {
+                    self.tray_unavailable = true;
+                    self.add_notification(
+                        Level::Warning,
+                        &format!(
+                            "Couldn't create the tray icon ({e}); closing the window quits \
+                             as usual."
+                        ),
+                    );
+                }
+            }
+        }
+        if std::mem::take(&mut self.hide_to_tray) {
+            hide_window(frame);
+        }
+        let Some(action) = self.tray.as_ref().and_then(Tray::poll) else {
+            return;
+        };
+        match action {
+            TrayAction::Show => show_window(frame),
+            TrayAction::ToggleTerminal if self.process.is_some() => {
+                self.mark_user_action("Stop");
+                self.stop_terminal();
+            }
+            TrayAction::ToggleTerminal => {
+                self.mark_user_action("Start");
+                self.request_launch(LaunchAction::Start);
+                // Asking whether to save unsaved config edits first needs the window.
+                if self.pending_launch.is_some() {
+                    show_window(frame);
+                }
+            }
+            TrayAction::Quit => {
+                self.quit_confirmed = true;
+                frame.close();
+            }
+        }
+    }
+
+    /// Re-run the readiness checklist shown on the Setup tab.
+    pub fn run_readiness_checks(&mut self) {
+        let java = self
+            .java_version()
+            .cloned()
+            .unwrap_or_else(|| Err("checking…".to_string()));
+        let jar_ok = !self.jar_path.is_empty() && Path::new(&self.jar_path).is_file();
+        let config_ok = !self.thetadata_config_path.is_empty()
+            && Path::new(&self.thetadata_config_path).is_file();
+        let port = self.rest_port;
+        let (port_ok, port_detail) = if self.process.is_some() {
+            (true, format!("{port} is used by the running terminal"))
+        } else if ports::port_available(port) {
+            (true, format!("{port} is free"))
+        } else {
+            (false, format!("{port} is already in use"))
+        };
+
+        self.readiness = Some(vec![
+            ReadinessCheck {
+                label: "Java",
+                passed: java.is_ok(),
+                detail: java.unwrap_or_else(|e| e),
+                hint: "Install a JRE 11+ and make sure `java` is on your PATH.",
+            },
+            ReadinessCheck {
+                label: "Jar path",
+                passed: jar_ok,
+                detail: if self.jar_path.is_empty() {
+                    "not set".to_string()
+                } else if jar_ok {
+                    self.jar_path.clone()
+                } else {
+                    format!("{} does not exist", self.jar_path)
+                },
+                hint: "Browse to ThetaTerminal.jar under ThetaTerminal Configuration.",
+            },
+            ReadinessCheck {
+                label: "Credentials",
+                passed: self.credentials_saved,
+                detail: if self.credentials_saved {
+                    "saved in keychain".to_string()
+                } else {
+                    "not saved".to_string()
+                },
+                hint: "Enter your username and password and click Save Credentials.",
+            },
+            ReadinessCheck {
+                label: "Config file",
+                passed: config_ok,
+                detail: if self.thetadata_config_path.is_empty() {
+                    "not set".to_string()
+                } else if config_ok {
+                    self.thetadata_config_path.clone()
+                } else {
+                    format!("{} does not exist", self.thetadata_config_path)
+                },
+                hint: "Pick a config in the Config tab, or start the terminal and use Get from Terminal.",
+            },
+            ReadinessCheck {
+                label: "Port",
+                passed: port_ok,
+                detail: port_detail,
+                hint: "Stop whatever holds the port (see Check port under Terminal Controls).",
+            },
+        ]);
+    }
+
+    /// Snapshot the persisted parts of the app state.
+    pub fn app_config(&self) -> AppConfig {
+        AppConfig {
+            jar_path: if self.jar_path.is_empty() {
+                None
+            } else {
+                Some(self.jar_path.clone())
+            },
+            java_path: if self.java_path.is_empty() {
+                None
+            } else {
+                Some(self.java_path.clone())
+            },
+            auto_start: self.auto_start,
+            auto_restart: self.auto_restart,
+            shutdown_grace_ms: self.shutdown_grace_ms,
+            on_gui_crash: self.on_gui_crash,
+            cache_credentials_in_memory: self.cache_credentials_in_memory,
+            profiles: self.profiles.clone(),
+            profile: self.profile.clone(),
+            default_tab: self.selected_tab,
+            thetadata_config_path: if self.thetadata_config_path.is_empty() {
+                None
+            } else {
+                Some(self.thetadata_config_path.clone())
+            },
+            follow_output: self.follow_output,
+            wrap_log_lines: self.wrap_log_lines,
+            lightweight_log_view: self.lightweight_log_view,
+            show_timestamps: self.show_timestamps,
+            log_timestamp_format: self.log_timestamp_format.clone(),
+            log_timestamp_utc: self.log_timestamp_utc,
+            order_output_by_time: self.order_output_by_time,
+            flood_threshold: self.flood_threshold,
+            max_log_lines: self.max_log_lines,
+            mark_user_actions: self.mark_user_actions,
+            strip_ansi_in_saved_log: self.strip_ansi_in_saved_log,
+            alert_sound_enabled: self.alert_sound_enabled,
+            alert_volume: self.alert_volume,
+            notify_on_exit: self.notify_on_exit,
+            rest_port: self.rest_port,
+            status_paths: self.status_paths(),
+            stats_path: self.stats_path.clone(),
+            monitor_host: self.monitor_host.clone(),
+            monitor_port: self.monitor_port,
+            theme: self.theme,
+            font_size: self.font_size,
+            min_window_width: self.min_window_width,
+            min_window_height: self.min_window_height,
+            capture_output_to_file: self.capture_output_to_file,
+            jvm_profile: self.jvm_profile,
+            jvm_args: self.jvm_args.clone(),
+            pass_system_timezone: self.pass_system_timezone,
+            auto_load_detected_config: self.auto_load_detected_config,
+            auto_load_delay_ms: self.auto_load_delay_ms,
+            save_panel_on_all_tabs: self.save_panel_on_all_tabs,
+            show_path_basenames: self.show_path_basenames,
+            confirm_start_with_unsaved_config: self.confirm_start_with_unsaved_config,
+            confirm_quit_while_running: self.confirm_quit_while_running,
+            minimize_to_tray: self.minimize_to_tray,
+            external_editor_command: self.external_editor_command.clone(),
+            backup_config_on_start: self.backup_config_on_start,
+            keep_backups: self.keep_backups,
+            backup_retention: self.backup_retention,
+            notes: self.notes.clone(),
+            check_for_updates: self.check_for_updates,
+            last_update_check: self.last_update_check,
+            available_update: self.available_update.as_ref().map(|(v, _)| v.clone()),
+            available_update_url: self.available_update.as_ref().map(|(_, u)| u.clone()),
+            recent_config_paths: self.recent_config_paths.clone(),
+            config_view_mode: self.config_view_mode,
+            config_line_numbers: self.config_line_numbers,
+            duplicate_key_policy: self.duplicate_key_policy,
+            log_to_file: self.log_to_file,
+            log_file_wrap: self.log_file_wrap,
+            log_file_wrap_width: self.log_file_wrap_width,
+            log_mirror_enabled: self.log_mirror_enabled,
+            log_mirror_port: self.log_mirror_port,
+            log_mirror_all_interfaces: self.log_mirror_all_interfaces,
+            window: self.window_geometry,
+            java_requirement: self.java_requirement.clone(),
+            macros: self.macros.clone(),
+        }
+    }
+
+    /// Write the current settings to a file the user picked.
+    pub fn export_settings(&mut self, path: &Path) {
+        match settings_io::export(path, &self.app_config()) {
+            Ok(()) => {
+                self.add_notification(
+                    Level::Info,
+                    &format!("Settings exported to {}", path.display()),
+                );
+            }
+            Err(e) => {
+                self.add_notification(Level::Error, &format!("Failed to export settings: {e}"));
+            }
+        }
+    }
+
+    /// Read a settings file and stage it for confirmation with a preview of what changes.
+    pub fn import_settings(&mut self, path: &Path) {
+        match settings_io::import(path) {
+            Ok(incoming) => {
+                let changes = settings_io::diff(&self.app_config(), &incoming);
+                if changes.is_empty() {
+                    self.add_notification(
+                        Level::Info,
+                        "Imported settings match the current ones; nothing to do.",
+                    );
+                } else {
+                    self.pending_settings_import = Some((incoming, changes));
+                }
+            }
+            Err(e) => {
+                self.add_notification(Level::Error, &format!("Failed to import settings: {e}"));
+            }
+        }
+    }
+
+    /// Take over every stored setting from `cfg`; the counterpart of `app_config()`.
+    pub fn apply_app_config(&mut self, cfg: AppConfig) {
+        let new_config_path = cfg.thetadata_config_path.unwrap_or_default();
+        self.jar_path = cfg.jar_path.unwrap_or_default();
+        self.java_path = cfg.java_path.unwrap_or_default();
+        self.auto_start = cfg.auto_start;
+        self.auto_restart = cfg.auto_restart;
+        self.shutdown_grace_ms = cfg.shutdown_grace_ms;
+        self.on_gui_crash = cfg.on_gui_crash;
+        self.cache_credentials_in_memory = cfg.cache_credentials_in_memory;
+        self.profiles = cfg.profiles;
+        self.select_profile(cfg.profile);
+        self.default_tab = cfg.default_tab;
+        self.follow_output = cfg.follow_output;
+        self.wrap_log_lines = cfg.wrap_log_lines;
+        self.lightweight_log_view = cfg.lightweight_log_view;
+        self.show_timestamps = cfg.show_timestamps;
+        self.log_timestamp_format = cfg.log_timestamp_format;
+        self.log_timestamp_utc = cfg.log_timestamp_utc;
+        self.order_output_by_time = cfg.order_output_by_time;
+        self.flood_threshold = cfg.flood_threshold;
+        self.max_log_lines = cfg.max_log_lines;
+        self.mark_user_actions = cfg.mark_user_actions;
+        self.strip_ansi_in_saved_log = cfg.strip_ansi_in_saved_log;
+        self.alert_sound_enabled = cfg.alert_sound_enabled;
+        self.alert_volume = cfg.alert_volume;
+        self.notify_on_exit = cfg.notify_on_exit;
+        self.rest_port = cfg.rest_port;
+        self.status_paths_text = cfg.status_paths.join("\n");
+        self.stats_path = cfg.stats_path;
+        self.monitor_host = cfg.monitor_host;
+        self.monitor_port = cfg.monitor_port;
+        self.theme = cfg.theme;
+        self.font_size = cfg.font_size;
+        self.min_window_width = cfg.min_window_width;
+        self.min_window_height = cfg.min_window_height;
+        self.capture_output_to_file = cfg.capture_output_to_file;
+        self.jvm_profile = cfg.jvm_profile;
+        self.jvm_args = cfg.jvm_args;
+        self.pass_system_timezone = cfg.pass_system_timezone;
+        self.auto_load_detected_config = cfg.auto_load_detected_config;
+        self.auto_load_delay_ms = cfg.auto_load_delay_ms;
+        self.save_panel_on_all_tabs = cfg.save_panel_on_all_tabs;
+        self.show_path_basenames = cfg.show_path_basenames;
+        self.confirm_start_with_unsaved_config = cfg.confirm_start_with_unsaved_config;
+        self.confirm_quit_while_running = cfg.confirm_quit_while_running;
+        self.minimize_to_tray = cfg.minimize_to_tray;
+        self.external_editor_command = cfg.external_editor_command;
+        self.backup_config_on_start = cfg.backup_config_on_start;
+        self.keep_backups = cfg.keep_backups;
+        self.backup_retention = cfg.backup_retention;
+        self.notes = cfg.notes;
+        self.check_for_updates = cfg.check_for_updates;
+        self.last_update_check = cfg.last_update_check;
+        self.available_update = cfg.available_update.zip(cfg.available_update_url);
+        self.recent_config_paths = cfg.recent_config_paths;
+        self.config_view_mode = cfg.config_view_mode;
+        self.config_line_numbers = cfg.config_line_numbers;
+        self.duplicate_key_policy = cfg.duplicate_key_policy;
+        self.log_to_file = cfg.log_to_file;
+        self.log_file_wrap = cfg.log_file_wrap;
+        self.log_file_wrap_width = cfg.log_file_wrap_width;
+        self.log_mirror_enabled = cfg.log_mirror_enabled;
+        self.log_mirror_port = cfg.log_mirror_port;
+        self.log_mirror_all_interfaces = cfg.log_mirror_all_interfaces;
+        self.window_geometry = cfg.window;
+        self.java_requirement = cfg.java_requirement;
+        self.macros = cfg.macros;
+
+        // Switching config files goes through the usual load so dirty tracking stays right.
+        if new_config_path != self.thetadata_config_path {
+            if self.config_is_dirty() {
+                self.add_notification(
+                    Level::Warning,
+                    "Kept the open config file because it has unsaved edits; the imported \
+                     config path was not loaded.",
+                );
+            } else if new_config_path.is_empty() {
+                self.thetadata_config_path.clear();
+            } else if let Err(e) = self.load_config_file(&new_config_path) {
+                self.add_notification(
+                    Level::Error,
+                    &format!("Failed to load imported config path: {e}"),
+                );
+            }
+        }
+    }
+
+    /// Save the current config file text. Returns whether the file was written.
+    pub fn save_current_config_file(&mut self) -> bool {
+        if self.thetadata_config_path.is_empty() {
+            self.add_notification(Level::Warning, "No config file path set.");
+            return false;
+        }
+        if !self.check_config_file_exists() {
+            return false;
+        }
+        if self.keep_backups {
+            self.backup_config_before_save();
+        }
+        let encoding = self.save_encoding();
+        match Self::write_thetadata_config_file(
+            &self.thetadata_config_path,
+            &self.thetadata_config_text,
+            encoding,
+        ) {
+            Ok(_) => {
+                let path = self.thetadata_config_path.clone();
+                self.note_saved_encoding(&path, encoding);
+                self.config_saved_text = self.thetadata_config_text.clone();
+                self.config_on_disk = true;
+                self.remember_config_mtime();
+                self.append_log("Config file saved.\n");
+                self.record_save_summary(&path);
+                true
+            }
+            Err(e) => {
+                self.add_notification(Level::Error, &format!("Failed to write config file: {e}"));
+                false
+            }
+        }
+    }
+
+    /// Before saving or refreshing: if the loaded file has been deleted since, ask whether to
+    /// recreate it or clear the editor instead of silently writing it back. Returns whether
+    /// the file is still there (or was never on disk).
+    pub fn check_config_file_exists(&mut self) -> bool {
+        if !self.config_on_disk || Path::new(&self.thetadata_config_path).exists() {
+            return true;
+        }
+        self.config_file_missing = true;
+        false
+    }
+
+    fn config_mtime(path: &str) -> Option<SystemTime> {
+        fs::metadata(path).and_then(|m| m.modified()).ok()
+    }
+
+    fn remember_config_mtime(&mut self) {
+        self.config_disk_mtime = Self::config_mtime(&self.thetadata_config_path);
+        self.config_changed_on_disk = false;
+        self.unsettled_disk_mtime = None;
+    }
+
+    /// Once a second, notice if the loaded config was modified outside the app (for example
+    /// by the terminal or an external editor). Without unsaved edits it is reloaded; with
+    /// them the Config tab offers to reload it. A new modification time has to hold for one
+    /// more check first, so a file still being written isn't read half-way.
+    fn watch_config_file(&mut self) {
+        if !self.config_on_disk
+            || self.config_changed_on_disk
+            || self.last_config_stat.elapsed() < Duration::from_secs(1)
+        {
+            return;
+        }
+        self.last_config_stat = Instant::now();
+        let mtime = Self::config_mtime(&self.thetadata_config_path);
+        if mtime.is_none() || mtime == self.config_disk_mtime {
+            self.unsettled_disk_mtime = None;
+            return;
+        }
+        if mtime != self.unsettled_disk_mtime {
+            self.unsettled_disk_mtime = mtime;
+            return;
+        }
+        if self.config_is_dirty() {
+            self.config_changed_on_disk = true;
+            self.add_notification(
+                Level::Warning,
+                "The config file changed on disk while you have unsaved edits; the Config tab \
+                 offers to reload it.",
+            );
+            return;
+        }
+        let path = self.thetadata_config_path.clone();
+        match self.load_config_file(&path) {
+            Ok(()) => {
+                self.add_notification(Level::Info, "The config file changed on disk; reloaded it.");
+            }
+            Err(e) => {
+                self.add_notification(
+                    Level::Error,
+                    &format!("Failed to reload the changed config: {e}"),
+                );
+                self.config_changed_on_disk = true;
+            }
+        }
+    }
+
+    /// Answer to the changed-on-disk banner: keep the editor as is and stop offering.
+    pub fn ignore_config_disk_change(&mut self) {
+        self.remember_config_mtime();
+    }
+
+    /// Open the current config in the configured external editor.
+    pub fn open_config_externally(&mut self) {
+        if self.thetadata_config_path.is_empty() {
+            self.add_notification(Level::Warning, "No config file path set.");
+            return;
+        }
+        let path = PathBuf::from(&self.thetadata_config_path);
+        if !path.is_file() {
+            self.add_notification(
+                Level::Error,
+                &format!("Config file not found: {}", self.thetadata_config_path),
+            );
+            return;
+        }
+        match external_editor::open_in_editor(&path, &self.external_editor_command) {
+            Ok(()) => {
+                self.add_notification(Level::Info,
+                "Opened config in external editor; changes saved there will be offered for reload.",
+            );
+            }
+            Err(e) => {
+                self.add_notification(
+                    Level::Error,
+                    &format!("Failed to open external editor: {e}"),
+                );
+            }
+        }
+    }
+
+    /// Answer to the missing-file prompt: write the editor contents back to the path.
+    pub fn recreate_missing_config(&mut self) {
+        self.config_file_missing = false;
+        self.config_on_disk = false;
+        if self.save_current_config_file() {
+            self.append_log("Config file recreated.\n");
+        }
+    }
+
+    /// Answer to the missing-file prompt: forget the deleted file and empty the editor.
+    pub fn clear_missing_config(&mut self) {
+        self.config_file_missing = false;
+        self.config_on_disk = false;
+        self.thetadata_config_path.clear();
+        self.thetadata_config_text.clear();
+        self.config_saved_text.clear();
+        self.reset_config_history();
+        self.config_encoding = ConfigEncoding::default();
+        self.save_config_as_utf8 = false;
+        self.structured_edits.clear();
+        self.pending_structured_apply = None;
+        self.append_log("Cleared the editor; the deleted config file was not recreated.\n");
+    }
+
+    /// Save the config and, if the terminal is running, restart it so the changes take effect.
+    pub fn apply_and_restart(&mut self) {
+        self.append_log("Apply & Restart: saving config…\n");
+        if !self.save_current_config_file() {
+            self.add_notification(
+                Level::Warning,
+                "Apply & Restart aborted: the config was not saved.",
+            );
+            return;
+        }
+        if self.process.is_some() {
+            self.append_log("Apply & Restart: restarting terminal…\n");
+            self.reset_terminal();
+        } else {
+            self.add_notification(
+                Level::Info,
+                "Terminal is not running; the new config applies on next start.",
+            );
+        }
+    }
+}
+
+impl eframe::App for ThetaApp {
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        let frame_start = Instant::now();
+        if self.applied_theme != Some(self.theme) {
+            ctx.set_visuals(theme::theme_visuals(self.theme));
+            self.applied_theme = Some(self.theme);
+        }
+        let font_size = self.font_size.clamp(FONT_SIZE_RANGE.0, FONT_SIZE_RANGE.1);
+        if self.applied_font_size != Some(font_size) {
+            let mut style = (*ctx.style()).clone();
+            style
+                .text_styles
+                .insert(egui::TextStyle::Monospace, FontId::monospace(font_size));
+            ctx.set_style(style);
+            self.applied_font_size = Some(font_size);
+        }
+        self.handle_shortcuts(ctx);
+
+        // Show the bottom panel (with Save button) when editing on the Config tab, and
+        // optionally on every tab while there are unsaved config changes.
+        let dirty = self.config_is_dirty();
+        let show_save_panel = self.selected_tab == Tab::Config
+            || (self.save_panel_on_all_tabs && dirty && !self.thetadata_config_path.is_empty());
+        if show_save_panel && self.config_view_mode != ConfigViewMode::ViewOnly {
+            eframe::egui::TopBottomPanel::bottom("global_bottom_panel").show(ctx, |ui| {
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    let can_save = dirty && !self.thetadata_config_path.is_empty();
+                    if ui
+                        .add_enabled(can_save, egui::Button::new("Save"))
+                        .clicked()
+                    {
+                        self.mark_user_action("Save");
+                        self.save_current_config_file();
+                    }
+                    let file_name = Path::new(&self.thetadata_config_path)
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string());
+                    match file_name {
+                        None => ui.weak("No config file selected"),
+                        Some(name) if dirty => ui.label(format!("{name} — unsaved changes")),
+                        Some(name) => ui.weak(format!("{name} — saved")),
+                    };
+                });
+                ui.add_space(6.0);
+            });
+        }
+
+        if self.pending_settings_import.is_some() {
+            let mut decision = None;
+            egui::Window::new("Import settings?")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    if let Some((_, changes)) = &self.pending_settings_import {
+                        ui.label(format!("{} setting(s) will change:", changes.len()));
+                        ScrollArea::vertical().max_height(240.0).show(ui, |ui| {
+                            egui::Grid::new("settings_import_preview")
+                                .num_columns(2)
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    for change in changes {
+                                        ui.monospace(&change.field);
+                                        ui.label(format!(
+                                            "{} → {}",
+                                            change.old_value, change.new_value
+                                        ));
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+                    }
+                    ui.horizontal(|ui| {
+                        if ui.button("Apply").clicked() {
+                            decision = Some(true);
+                        }
+                        if ui.button("Cancel").clicked() {
+                            decision = Some(false);
+                        }
+                    });
+                });
+            if let Some(apply) = decision {
+                if let Some((incoming, _)) = self.pending_settings_import.take() {
+                    if apply {
+                        self.apply_app_config(incoming);
+                        self.add_notification(Level::Info, "Imported settings applied.");
+                    }
+                }
+            }
+        }
+
+        if let Some(conflicts) = self.jvm_arg_conflicts.clone() {
+            egui::Window::new("Conflicting JVM arguments")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label("These JVM arguments clash with values the app manages:");
+                    for conflict in &conflicts {
+                        ui.label(format!("• {conflict}"));
+                    }
+                    ui.horizontal(|ui| {
+                        if ui.button("Start anyway").clicked() {
+                            self.jvm_arg_conflicts = None;
+                            self.launch_despite_conflicts = true;
+                            self.start_terminal();
+                        }
+                        if ui.button("Cancel").clicked() {
+                            self.jvm_arg_conflicts = None;
+                        }
+                    });
+                });
+        }
+
+        if self.confirm_reset {
+            egui::Window::new("Reset terminal?")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label("The running terminal will be killed and started again.");
+                    ui.checkbox(
+                        &mut self.skip_restart_confirmation,
+                        "Skip restart confirmation for this session (enables ⟳)",
+                    );
+                    ui.horizontal(|ui| {
+                        if ui.button("Reset").clicked() {
+                            self.confirm_reset = false;
+                            self.request_launch(LaunchAction::Reset);
+                        }
+                        if ui.button("Cancel").clicked() {
+                            self.confirm_reset = false;
+                        }
+                    });
+                });
+        }
+
+        if self.confirm_quit {
+            egui::Window::new("Quit?")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label("Terminal is running. Quit anyway?");
+                    ui.weak("Quitting stops the terminal.");
+                    let mut dont_ask = !self.confirm_quit_while_running;
+                    ui.checkbox(&mut dont_ask, "Don't ask again");
+                    self.confirm_quit_while_running = !dont_ask;
+                    ui.horizontal(|ui| {
+                        if ui.button("Quit").clicked() {
+                            self.confirm_quit = false;
+                            self.quit_confirmed = true;
+                            frame.close();
+                        }
+                        if ui.button("Cancel").clicked() {
+                            self.confirm_quit = false;
+                        }
+                    });
+                });
+        }
+
+        if let Some(action) = self.pending_launch {
+            egui::Window::new("Unsaved config changes")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label("You have unsaved config changes — save before starting?");
+                    ui.horizontal(|ui| {
+                        if ui.button("Save and start").clicked() {
+                            self.pending_launch = None;
+                            if self.save_current_config_file() {
+                                self.launch(action);
+                            }
+                        }
+                        if ui.button("Start anyway").clicked() {
+                            self.pending_launch = None;
+                            self.launch(action);
+                        }
+                        if ui.button("Cancel").clicked() {
+                            self.pending_launch = None;
+                        }
+                    });
+                });
+        }
+
+        if self.config_file_missing {
+            egui::Window::new("Config file missing")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(format!(
+                        "The config file no longer exists on disk:\n{}",
+                        self.thetadata_config_path
+                    ));
+                    ui.label("Recreate it on save, or clear the editor?");
+                    ui.horizontal(|ui| {
+                        if ui.button("Recreate").clicked() {
+                            self.recreate_missing_config();
+                        }
+                        if ui.button("Clear editor").clicked() {
+                            self.clear_missing_config();
+                        }
+                        if ui.button("Cancel").clicked() {
+                            self.config_file_missing = false;
+                        }
+                    });
+                });
+        }
+
+        eframe::egui::CentralPanel::default().show(ctx, |ui| {
+            // In a very small window, drop the title so the tabs and their controls stay visible.
+            let compact = ui.available_width() < 260.0 || ui.available_height() < 200.0;
+            if !compact {
+                ui.add_space(4.0);
+                ui.heading(egui::RichText::new("ThetaData GUI Wrapper").strong());
+                ui.add_space(8.0);
+            }
+
+            self.notifications.expire();
+            let mut dismissed = None;
+            for notification in self.notifications.iter() {
+                let color = match notification.level {
+                    Level::Info => ui.visuals().text_color(),
+                    Level::Warning => ui.visuals().warn_fg_color,
+                    Level::Error => Color32::RED,
+                };
+                ui.horizontal_wrapped(|ui| {
+                    ui.colored_label(color, &notification.text);
+                    if notification.dismissible && ui.small_button("Dismiss").clicked() {
+                        dismissed = Some(notification.id);
+                    }
+                });
+            }
+            if let Some(id) = dismissed {
+                self.notifications.dismiss(id);
+            }
+            if self.notifications.iter().next().is_some() {
+                ui.add_space(8.0);
+            }
+
+            // The last result is cached across restarts, so only show it while checks are on.
+            if self.check_for_updates && !self.update_banner_dismissed {
+                if let Some((version, url)) = self.available_update.clone() {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label(format!("Update available: v{version}"));
+                        ui.hyperlink_to("What's new", url);
+                        if ui.small_button("Dismiss").clicked() {
+                            self.update_banner_dismissed = true;
+                        }
+                    });
+                    ui.add_space(8.0);
+                }
+            }
+
+            ui.with_layout(
+                egui::Layout::top_down_justified(egui::Align::Center),
+                |ui| {
+                    // Wrap the tab buttons onto a second row when the window is too narrow.
+                    ui.horizontal_wrapped(|ui| {
                         ui.spacing_mut().item_spacing.x = 8.0;
                         let button_size = Vec2::new(50.0, 18.0);
 
@@ -284,7 +3499,7 @@ impl eframe::App for ThetaApp {
                                     .stroke(egui::Stroke::new(1.0, ui.visuals().text_color())),
                             )
                         };
-                        if setup_btn.clicked() {
+                        if setup_btn.on_hover_text("Ctrl+1").clicked() {
                             self.selected_tab = Tab::Setup;
                         }
 
@@ -298,28 +3513,34 @@ impl eframe::App for ThetaApp {
                                     .stroke(egui::Stroke::new(1.0, ui.visuals().text_color())),
                             )
                         };
-                        if terminal_btn.clicked() {
+                        if terminal_btn.on_hover_text("Ctrl+2").clicked() {
                             self.selected_tab = Tab::Terminal;
                         }
 
+                        // Tab switches keep the editor text; the asterisk is a reminder to save.
+                        let config_label = if self.config_is_dirty() {
+                            "Config*"
+                        } else {
+                            "Config"
+                        };
                         let config_btn = if self.selected_tab == Tab::Config {
-                            ui.add_sized(button_size, egui::Button::new("Config"))
+                            ui.add_sized(button_size, egui::Button::new(config_label))
                         } else {
                             ui.add_sized(
                                 button_size,
-                                egui::Button::new("Config")
+                                egui::Button::new(config_label)
                                     .fill(Color32::TRANSPARENT)
                                     .stroke(egui::Stroke::new(1.0, ui.visuals().text_color())),
                             )
                         };
-                        if config_btn.clicked() {
+                        if config_btn.on_hover_text("Ctrl+3").clicked() {
                             self.selected_tab = Tab::Config;
                         }
                     });
                 },
             );
 
-            ui.add_space(8.0);
+            ui.add_space(if compact { 2.0 } else { 8.0 });
 
             ScrollArea::vertical()
                 .auto_shrink([false; 2])
@@ -330,46 +3551,149 @@ impl eframe::App for ThetaApp {
                 });
         });
 
-        let new_lines: Vec<String> = if let Some(rx) = &self.log_receiver {
-            rx.try_iter().collect()
-        } else {
-            Vec::new()
-        };
-        for line in new_lines {
-            self.append_log(&line);
-            self.append_log("\n");
-            self.detect_config_file_path_in_line(&line);
+        if let Some(status) = self.supervise() {
+            self.schedule_crash_restart(status);
         }
+        self.poll_crash_restart();
 
-        if let Some(child) = &mut self.process {
-            if let Ok(Some(_status)) = child.try_wait() {
-                self.append_log("Terminal process exited.\n");
-                self.process = None;
+        let title = format!("ThetaData Terminal GUI — {}", self.status_summary());
+        if title != self.window_title {
+            frame.set_window_title(&title);
+            self.window_title = title;
+            if let Some(tray) = &self.tray {
+                tray.update(&self.status_summary(), self.process.is_some());
             }
         }
+        self.handle_tray(ctx, frame);
 
-        let new_cfg = AppConfig {
-            jar_path: if self.jar_path.is_empty() {
-                None
-            } else {
-                Some(self.jar_path.clone())
-            },
-            auto_start: self.auto_start,
-            default_tab: self.selected_tab,
-            thetadata_config_path: if self.thetadata_config_path.is_empty() {
-                None
-            } else {
-                Some(self.thetadata_config_path.clone())
-            },
-        };
+        self.track_window_geometry(frame);
+        let new_cfg = self.app_config();
         if let Err(e) = confy::store("thetadata_terminal_manager", None, new_cfg) {
             self.append_log(&format!("Failed saving app config: {e}\n"));
         }
 
+        self.track_config_edits();
+        if self.frame_times.len() == FRAME_TIME_SAMPLES {
+            self.frame_times.pop_front();
+        }
+        self.frame_times.push_back(frame_start.elapsed());
         ctx.request_repaint();
     }
 
+    /// Hold the close while the terminal runs: hide to the tray when that is enabled,
+    /// otherwise wait until the quit prompt is confirmed.
+    fn on_close_event(&mut self) -> bool {
+        if self.quit_confirmed {
+            return true;
+        }
+        if self.minimize_to_tray && self.tray.is_some() && self.process.is_some() {
+            self.hide_to_tray = true;
+            return false;
+        }
+        if self.process.is_none() || !self.confirm_quit_while_running {
+            return true;
+        }
+        self.confirm_quit = true;
+        false
+    }
+
     fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
         self.force_quit_process();
+        self.cached_credentials = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_credentials_are_not_usable() {
+        assert!(credentials_usable("trader@example.com", "hunter2"));
+        assert!(!credentials_usable("", "hunter2"));
+        assert!(!credentials_usable("   ", "hunter2"));
+        assert!(!credentials_usable("trader@example.com", ""));
+        assert!(!credentials_usable("", ""));
+    }
+
+    /// An app whose terminal was started with known credentials and whose log file goes to
+    /// a fresh directory under `name`.
+    fn app_with_credentials(name: &str) -> (ThetaApp, PathBuf) {
+        let dir = std::env::temp_dir().join(format!("thetadata_{name}_{}", std::process::id()));
+        let mut app = ThetaApp::new();
+        app.headless = false;
+        app.log_file_wrap = false;
+        app.log_buffer.clear();
+        app.file_log = Some(FileLog::open(dir.clone()).unwrap());
+        app.redactor = Redactor::new(["me@example.com", "s3cret"]);
+        (app, dir)
+    }
+
+    #[test]
+    fn credentials_masked_in_log_buffer() {
+        let (mut app, dir) = app_with_credentials("redact_buffer");
+        let line = TerminalLine::now("Logging in as me@example.com with s3cret".to_string());
+        app.ingest_terminal_line(line, true);
+        let _ = fs::remove_dir_all(&dir);
+        let text = app.log_buffer.text();
+        assert!(!text.contains("me@example.com") && !text.contains("s3cret"));
+        assert!(text.ends_with("Logging in as **** with ****\n"));
+    }
+
+    #[test]
+    fn credentials_masked_in_log_file() {
+        let (mut app, dir) = app_with_credentials("redact_file");
+        // A line the flood guard keeps off the screen still reaches the file.
+        let line = TerminalLine::now("Logging in as me@example.com with s3cret".to_string());
+        app.ingest_terminal_line(line, false);
+        let path = app.file_log.as_ref().unwrap().current_path();
+        let written = fs::read_to_string(path).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        assert!(!written.contains("me@example.com") && !written.contains("s3cret"));
+        assert!(written.contains("Logging in as **** with ****"));
+    }
+
+    #[test]
+    fn status_summary_when_stopped() {
+        let uptime = Some(Duration::from_secs(90));
+        assert_eq!(
+            format_status_summary(false, Some("CONNECTED"), Some("work"), uptime),
+            "Stopped"
+        );
+    }
+
+    #[test]
+    fn status_summary_while_starting() {
+        assert_eq!(format_status_summary(true, None, None, None), "Running");
+    }
+
+    #[test]
+    fn status_summary_with_profile_and_uptime() {
+        assert_eq!(
+            format_status_summary(
+                true,
+                Some("CONNECTED"),
+                Some("work"),
+                Some(Duration::from_secs(2 * 3600 + 5 * 60 + 30)),
+            ),
+            "Running · CONNECTED · work · 2h 5m uptime"
+        );
+    }
+
+    #[test]
+    fn status_summary_uptime_units() {
+        let summary =
+            |secs| format_status_summary(true, None, None, Some(Duration::from_secs(secs)));
+        assert_eq!(summary(59), "Running · 0m uptime");
+        assert_eq!(summary(12 * 60), "Running · 12m uptime");
+        assert_eq!(summary(27 * 3600), "Running · 1d 3h uptime");
+    }
+
+    #[test]
+    fn status_summary_when_disconnected() {
+        assert_eq!(
+            format_status_summary(true, Some("Disconnected"), None, Some(Duration::ZERO)),
+            "Running · Disconnected · 0m uptime"
+        );
     }
 }
@@ -0,0 +1,71 @@
+//! Messages shown as a stack above the tabs. Info messages hide themselves after a while;
+//! warnings and errors stay until dismissed, or until whatever raised them clears them.
+
+use std::time::{Duration, Instant};
+
+/// How long an info notification stays up.
+const INFO_DURATION: Duration = Duration::from_secs(8);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Level {
+    Info,
+    Warning,
+    Error,
+}
+
+pub struct Notification {
+    pub id: u64,
+    pub level: Level,
+    pub text: String,
+    pub created_at: Instant,
+    pub dismissible: bool, // false for conditions the app clears itself, like a lost connection
+}
+
+#[derive(Default)]
+pub struct Notifications {
+    items: Vec<Notification>,
+    next_id: u64,
+}
+
+impl Notifications {
+    /// Show a message and return its id. An identical active message is moved to the top
+    /// and its timer restarted instead of being shown twice.
+    pub fn add(&mut self, level: Level, text: &str, dismissible: bool) -> u64 {
+        if let Some(index) = self
+            .items
+            .iter()
+            .position(|n| n.level == level && n.text == text)
+        {
+            let mut existing = self.items.remove(index);
+            existing.created_at = Instant::now();
+            existing.dismissible |= dismissible;
+            let id = existing.id;
+            self.items.push(existing);
+            return id;
+        }
+        self.next_id += 1;
+        self.items.push(Notification {
+            id: self.next_id,
+            level,
+            text: text.to_string(),
+            created_at: Instant::now(),
+            dismissible,
+        });
+        self.next_id
+    }
+
+    pub fn dismiss(&mut self, id: u64) {
+        self.items.retain(|n| n.id != id);
+    }
+
+    /// Drop info messages that have been up long enough.
+    pub fn expire(&mut self) {
+        self.items
+            .retain(|n| n.level != Level::Info || n.created_at.elapsed() < INFO_DURATION);
+    }
+
+    /// Active notifications, newest first.
+    pub fn iter(&self) -> impl Iterator<Item = &Notification> {
+        self.items.iter().rev()
+    }
+}
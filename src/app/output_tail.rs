@@ -0,0 +1,71 @@
+use std::{
+    fs,
+    io::{Read, Seek, SeekFrom},
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+/// How often the redirected output file is checked for new lines.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Once this much has been read, the file is emptied so it doesn't grow for as long as the
+/// terminal runs. Everything read has already reached the screen and the log sinks.
+const MAX_FILE_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Follows the file the terminal writes to when its output is redirected away from the pipes.
+pub struct OutputTail {
+    pub path: PathBuf,
+    offset: u64,
+    partial: Vec<u8>,
+    last_poll: Instant,
+}
+
+impl OutputTail {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            offset: 0,
+            partial: Vec::new(),
+            last_poll: Instant::now(),
+        }
+    }
+
+    /// Return the complete lines appended since the last poll.
+    /// Unless `force` is set, this is throttled to once per `POLL_INTERVAL`.
+    pub fn poll(&mut self, force: bool) -> std::io::Result<Vec<String>> {
+        if !force && self.last_poll.elapsed() < POLL_INTERVAL {
+            return Ok(Vec::new());
+        }
+        self.last_poll = Instant::now();
+
+        let mut file = fs::File::open(&self.path)?;
+        // Emptied or replaced since the last poll: start over from the beginning.
+        if file.metadata()?.len() < self.offset {
+            self.offset = 0;
+        }
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)?;
+        self.offset += bytes.len() as u64;
+        self.partial.extend_from_slice(&bytes);
+        if self.offset >= MAX_FILE_BYTES {
+            // The terminal writes in append mode, so it carries on at the new end. Output
+            // written between the read above and this call is lost.
+            fs::OpenOptions::new()
+                .write(true)
+                .open(&self.path)?
+                .set_len(0)?;
+            self.offset = 0;
+        }
+
+        // Keep an unterminated trailing line for the next poll.
+        let Some(end) = self.partial.iter().rposition(|&b| b == b'\n') else {
+            return Ok(Vec::new());
+        };
+        let complete: Vec<u8> = self.partial.drain(..=end).collect();
+        Ok(String::from_utf8_lossy(&complete)
+            .lines()
+            .map(str::to_string)
+            .collect())
+    }
+}
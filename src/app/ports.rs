@@ -0,0 +1,113 @@
+//! Diagnostics for the local port ThetaTerminal listens on.
+
+use super::util::hidden_command;
+use std::{net::TcpListener, process::Command};
+
+/// The process currently listening on a port.
+#[derive(Clone)]
+pub struct PortHolder {
+    pub port: u16,
+    pub pid: u32,
+    pub name: String,
+}
+
+impl PortHolder {
+    pub fn describe(&self) -> String {
+        format!(
+            "Port {} is held by {} (PID {})",
+            self.port, self.name, self.pid
+        )
+    }
+
+    /// Whether the holder looks like another ThetaTerminal (i.e. a Java process).
+    pub fn is_theta_terminal(&self) -> bool {
+        self.name.to_ascii_lowercase().contains("java")
+    }
+}
+
+/// True when nothing is bound to `port` on localhost.
+pub fn port_available(port: u16) -> bool {
+    TcpListener::bind(("127.0.0.1", port)).is_ok()
+}
+
+/// Find the process listening on `port` via `lsof`.
+#[cfg(not(target_os = "windows"))]
+pub fn find_port_holder(port: u16) -> Option<PortHolder> {
+    // `-F pc` prints one field per line: `p<pid>` followed by `c<command>`.
+    let output = Command::new("lsof")
+        .args(["-nP", &format!("-iTCP:{port}"), "-sTCP:LISTEN", "-Fpc"])
+        .output()
+        .ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    let mut pid = None;
+    for line in text.lines() {
+        if let Some(p) = line.strip_prefix('p') {
+            pid = p.parse().ok();
+        } else if let Some(name) = line.strip_prefix('c') {
+            return Some(PortHolder {
+                port,
+                pid: pid?,
+                name: name.to_string(),
+            });
+        }
+    }
+    None
+}
+
+/// Find the process listening on `port` via `netstat` and `tasklist`.
+#[cfg(target_os = "windows")]
+pub fn find_port_holder(port: u16) -> Option<PortHolder> {
+    let output = hidden_command("netstat")
+        .args(["-ano", "-p", "TCP"])
+        .output()
+        .ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    let suffix = format!(":{port}");
+    // Columns: Proto, Local Address, Foreign Address, State, PID
+    let pid: u32 = text.lines().find_map(|line| {
+        let cols: Vec<&str> = line.split_whitespace().collect();
+        if cols.len() == 5 && cols[1].ends_with(&suffix) && cols[3] == "LISTENING" {
+            cols[4].parse().ok()
+        } else {
+            None
+        }
+    })?;
+
+    let tasks = hidden_command("tasklist")
+        .args(["/FI", &format!("PID eq {pid}"), "/FO", "CSV", "/NH"])
+        .output()
+        .ok()?;
+    let name = String::from_utf8_lossy(&tasks.stdout)
+        .split(',')
+        .next()
+        .map(|s| s.trim().trim_matches('"').to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "unknown process".to_string());
+    Some(PortHolder { port, pid, name })
+}
+
+/// Terminate a process by PID.
+pub fn kill_process(pid: u32) -> std::io::Result<bool> {
+    let status = if cfg!(target_os = "windows") {
+        hidden_command("taskkill")
+            .args(["/PID", &pid.to_string(), "/F"])
+            .status()?
+    } else {
+        Command::new("kill").arg(pid.to_string()).status()?
+    };
+    Ok(status.success())
+}
+
+/// Ask a process to exit: SIGTERM on Unix, a plain (non-forced) `taskkill` on Windows.
+pub fn request_terminate(pid: u32) -> std::io::Result<bool> {
+    let status = if cfg!(target_os = "windows") {
+        hidden_command("taskkill")
+            .args(["/PID", &pid.to_string()])
+            .status()?
+    } else {
+        Command::new("kill")
+            .args(["-TERM", &pid.to_string()])
+            .status()?
+    };
+    Ok(status.success())
+}
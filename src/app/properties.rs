@@ -0,0 +1,295 @@
+//! Helpers for the `key=value` properties format ThetaTerminal uses for its config file.
+
+use std::ops::RangeInclusive;
+
+/// Values ThetaTerminal ships with for the keys users most often change. Keys not listed
+/// here have no known default.
+const SCHEMA_DEFAULTS: &[(&str, &str)] = &[
+    ("HTTP_PORT", "25510"),
+    ("WS_PORT", "25520"),
+    ("MDDS_REGION", "MDDS_NJ_HOSTS"),
+    ("FPSS_REGION", "FPSS_NJ_HOSTS"),
+];
+
+/// One `key=value` line from the config text.
+#[derive(Clone)]
+pub struct ConfigEntry {
+    /// Zero-based line number within the text.
+    pub line: usize,
+    pub key: String,
+    pub value: String,
+}
+
+/// A staged value change for a single key.
+#[derive(Clone)]
+pub struct ConfigChange {
+    pub line: usize,
+    pub key: String,
+    pub old_value: String,
+    pub new_value: String,
+}
+
+/// Collect every `key=value` line, skipping blanks and `#`/`!` comments.
+pub fn parse_entries(text: &str) -> Vec<ConfigEntry> {
+    text.lines()
+        .enumerate()
+        .filter_map(|(line, raw)| {
+            let trimmed = raw.trim_start();
+            if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with('!') {
+                return None;
+            }
+            let (key, value) = trimmed.split_once('=')?;
+            Some(ConfigEntry {
+                line,
+                key: key.trim().to_string(),
+                value: value.trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Replace the value on `line`, keeping the key, its spacing and the line ending intact.
+pub fn set_value(text: &str, line: usize, value: &str) -> String {
+    let mut out = String::with_capacity(text.len() + value.len());
+    for (idx, chunk) in text.split_inclusive('\n').enumerate() {
+        if idx == line {
+            if let Some(eq) = chunk.find('=') {
+                let body_end = chunk.trim_end_matches(['\r', '\n']).len();
+                let old_value = &chunk[eq + 1..body_end];
+                let value_start = body_end - old_value.trim_start().len();
+                out.push_str(&chunk[..value_start]);
+                out.push_str(value);
+                out.push_str(&chunk[body_end..]);
+                continue;
+            }
+        }
+        out.push_str(chunk);
+    }
+    out
+}
+
+/// Keys set on more than one line, each with all of its line numbers, in file order.
+pub fn duplicate_keys(entries: &[ConfigEntry]) -> Vec<(String, Vec<usize>)> {
+    let mut keys: Vec<(String, Vec<usize>)> = Vec::new();
+    for entry in entries {
+        match keys.iter_mut().find(|(key, _)| *key == entry.key) {
+            Some((_, lines)) => lines.push(entry.line),
+            None => keys.push((entry.key.clone(), vec![entry.line])),
+        }
+    }
+    keys.retain(|(_, lines)| lines.len() > 1);
+    keys
+}
+
+/// Lines of the duplicates that lose when only the first (or last) occurrence is kept.
+pub fn losing_duplicate_lines(duplicates: &[(String, Vec<usize>)], keep_last: bool) -> Vec<usize> {
+    let mut lines: Vec<usize> = duplicates
+        .iter()
+        .flat_map(|(_, lines)| {
+            let kept = if keep_last { lines.len() - 1 } else { 0 };
+            lines
+                .iter()
+                .enumerate()
+                .filter(move |(idx, _)| *idx != kept)
+                .map(|(_, line)| *line)
+        })
+        .collect();
+    lines.sort_unstable();
+    lines
+}
+
+/// Drop whole `lines` (sorted) from `text`; comments and every other line are untouched.
+pub fn remove_lines(text: &str, lines: &[usize]) -> String {
+    text.split_inclusive('\n')
+        .enumerate()
+        .filter(|(idx, _)| lines.binary_search(idx).is_err())
+        .map(|(_, chunk)| chunk)
+        .collect()
+}
+
+/// Comment out `lines` (inclusive, zero-based) with `#`, or uncomment them if every
+/// non-blank one already is, with `#` or `!`. The `#` goes after each line's indentation;
+/// blank lines are left alone.
+pub fn toggle_comments(text: &str, lines: RangeInclusive<usize>) -> String {
+    let chunks: Vec<&str> = text.split_inclusive('\n').collect();
+    let selected = || {
+        chunks
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| lines.contains(idx))
+            .map(|(_, chunk)| *chunk)
+            .filter(|chunk| !chunk.trim().is_empty())
+    };
+    let uncomment = selected().next().is_some()
+        && selected().all(|chunk| chunk.trim_start().starts_with(['#', '!']));
+    chunks
+        .iter()
+        .enumerate()
+        .map(|(idx, chunk)| {
+            if !lines.contains(&idx) || chunk.trim().is_empty() {
+                return chunk.to_string();
+            }
+            let indent = chunk.len() - chunk.trim_start().len();
+            let (lead, rest) = chunk.split_at(indent);
+            if uncomment {
+                format!("{lead}{}", &rest[1..])
+            } else {
+                format!("{lead}#{rest}")
+            }
+        })
+        .collect()
+}
+
+/// The REST port the config sets with `HTTP_PORT`, if present and a valid port number.
+pub fn http_port(entries: &[ConfigEntry]) -> Option<u16> {
+    entries
+        .iter()
+        .rev()
+        .find(|e| e.key == "HTTP_PORT")?
+        .value
+        .parse()
+        .ok()
+        .filter(|port| *port != 0)
+}
+
+/// The shipped default for `key`, if known.
+pub fn schema_default(key: &str) -> Option<&'static str> {
+    SCHEMA_DEFAULTS
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, v)| *v)
+}
+
+/// One-line summary such as `MDDS_REGION MDDS_NJ_HOSTS→MDDS_STAGE_HOSTS, HTTP_PORT 25510→25520`.
+pub fn describe_changes(changes: &[ConfigChange]) -> String {
+    changes
+        .iter()
+        .map(|c| format!("{} {}→{}", c.key, c.old_value, c.new_value))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Region names that have both an `MDDS_<REGION>_HOSTS` and an `FPSS_<REGION>_HOSTS` list.
+pub fn region_names(entries: &[ConfigEntry]) -> Vec<String> {
+    let has_key = |key: &str| entries.iter().any(|e| e.key == key);
+    let mut regions: Vec<String> = entries
+        .iter()
+        .filter_map(|e| {
+            e.key
+                .strip_prefix("MDDS_")?
+                .strip_suffix("_HOSTS")
+                .map(str::to_string)
+        })
+        .filter(|region| has_key(&format!("FPSS_{region}_HOSTS")))
+        .collect();
+    regions.dedup();
+    regions
+}
+
+/// Entries whose key mentions one of `regions` as a whole `_`-separated part, grouped per
+/// region in the order the regions are given. Everything else is returned first as the
+/// general section. Entries keep their file order within each group.
+pub fn group_by_region(
+    entries: &[ConfigEntry],
+    regions: &[String],
+) -> (Vec<ConfigEntry>, Vec<(String, Vec<ConfigEntry>)>) {
+    let mut general = Vec::new();
+    let mut sections: Vec<(String, Vec<ConfigEntry>)> =
+        regions.iter().map(|r| (r.clone(), Vec::new())).collect();
+    for entry in entries {
+        // The longest match wins so e.g. `NJ_STAGE` isn't filed under `NJ`.
+        let region = regions
+            .iter()
+            .enumerate()
+            .filter(|(_, region)| key_mentions(&entry.key, region))
+            .max_by_key(|(_, region)| region.len());
+        match region {
+            Some((index, _)) => sections[index].1.push(entry.clone()),
+            None => general.push(entry.clone()),
+        }
+    }
+    (general, sections)
+}
+
+fn key_mentions(key: &str, region: &str) -> bool {
+    key == region
+        || key.starts_with(&format!("{region}_"))
+        || key.ends_with(&format!("_{region}"))
+        || key.contains(&format!("_{region}_"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toggle_keeps_indentation() {
+        let text = "  HTTP_PORT=25510\n\tWS_PORT=25520\n";
+        let commented = toggle_comments(text, 0..=1);
+        assert_eq!(commented, "  #HTTP_PORT=25510\n\t#WS_PORT=25520\n");
+        assert_eq!(toggle_comments(&commented, 0..=1), text);
+    }
+
+    #[test]
+    fn toggle_mixed_selection_comments_everything() {
+        let text = "#HTTP_PORT=25510\nWS_PORT=25520\n\nMDDS_REGION=MDDS_NJ_HOSTS\n";
+        assert_eq!(
+            toggle_comments(text, 0..=3),
+            "##HTTP_PORT=25510\n#WS_PORT=25520\n\n#MDDS_REGION=MDDS_NJ_HOSTS\n"
+        );
+    }
+
+    #[test]
+    fn toggle_only_touches_selected_lines() {
+        let text = "A=1\nB=2\nC=3";
+        assert_eq!(toggle_comments(text, 1..=1), "A=1\n#B=2\nC=3");
+    }
+
+    #[test]
+    fn toggle_uncomments_bang_comments() {
+        let text = "!HTTP_PORT=25510\r\n  # WS_PORT=25520\r\n";
+        assert_eq!(
+            toggle_comments(text, 0..=1),
+            "HTTP_PORT=25510\r\n   WS_PORT=25520\r\n"
+        );
+    }
+
+    const DUPLICATED: &str = "# REST port\nHTTP_PORT=25510\nWS_PORT=25520\n\
+                              ! older value\nHTTP_PORT=25511\nHTTP_PORT=25512\n";
+
+    fn dedupe(text: &str, keep_last: bool) -> String {
+        let duplicates = duplicate_keys(&parse_entries(text));
+        remove_lines(text, &losing_duplicate_lines(&duplicates, keep_last))
+    }
+
+    #[test]
+    fn duplicate_keys_lists_every_line() {
+        assert_eq!(
+            duplicate_keys(&parse_entries(DUPLICATED)),
+            [("HTTP_PORT".to_string(), vec![1, 4, 5])]
+        );
+    }
+
+    #[test]
+    fn keep_first_keeps_first_occurrence_and_comments() {
+        assert_eq!(
+            dedupe(DUPLICATED, false),
+            "# REST port\nHTTP_PORT=25510\nWS_PORT=25520\n! older value\n"
+        );
+    }
+
+    #[test]
+    fn keep_last_keeps_last_occurrence_and_comments() {
+        assert_eq!(
+            dedupe(DUPLICATED, true),
+            "# REST port\nWS_PORT=25520\n! older value\nHTTP_PORT=25512\n"
+        );
+    }
+
+    #[test]
+    fn commented_out_keys_are_not_duplicates() {
+        let text = "HTTP_PORT=25510\n#HTTP_PORT=25511\n";
+        assert!(duplicate_keys(&parse_entries(text)).is_empty());
+        assert_eq!(dedupe(text, true), text);
+    }
+}
@@ -0,0 +1,78 @@
+//! Masks the saved credentials in terminal output, which some terminal versions echo back.
+
+use zeroize::Zeroizing;
+
+/// What a masked credential is replaced with.
+const MASK: &str = "****";
+
+/// Anything shorter is too likely to be an ordinary substring to mask safely.
+const MIN_SECRET_LEN: usize = 3;
+
+#[derive(Default)]
+pub struct Redactor {
+    secrets: Vec<Zeroizing<String>>, // wiped when the redactor is replaced
+}
+
+impl Redactor {
+    pub fn new<'a>(secrets: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut secrets: Vec<Zeroizing<String>> = secrets
+            .into_iter()
+            .filter(|s| s.len() >= MIN_SECRET_LEN)
+            .map(|s| Zeroizing::new(s.to_string()))
+            .collect();
+        // Longest first, so a password containing the username is masked whole.
+        secrets.sort_by_key(|s| std::cmp::Reverse(s.len()));
+        Self { secrets }
+    }
+
+    /// Replace every occurrence of a secret in `text`.
+    pub fn redact(&self, text: &mut String) {
+        for secret in &self.secrets {
+            if text.contains(secret.as_str()) {
+                *text = text.replace(secret.as_str(), MASK);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn redacted(redactor: &Redactor, text: &str) -> String {
+        let mut text = text.to_string();
+        redactor.redact(&mut text);
+        text
+    }
+
+    #[test]
+    fn masks_every_occurrence() {
+        let redactor = Redactor::new(["hunter2"]);
+        assert_eq!(
+            redacted(&redactor, "pass=hunter2 again hunter2"),
+            "pass=**** again ****"
+        );
+    }
+
+    #[test]
+    fn masks_longest_secret_first() {
+        // The password contains the username; masking the username first would leave "****99".
+        let redactor = Redactor::new(["trader", "trader99"]);
+        assert_eq!(
+            redacted(&redactor, "user trader pass trader99"),
+            "user **** pass ****"
+        );
+    }
+
+    #[test]
+    fn ignores_secrets_below_min_len() {
+        let short = "x".repeat(MIN_SECRET_LEN - 1);
+        let redactor = Redactor::new([short.as_str()]);
+        let line = format!("{short} stays");
+        assert_eq!(redacted(&redactor, &line), line);
+
+        let exact = "y".repeat(MIN_SECRET_LEN);
+        let redactor = Redactor::new([exact.as_str()]);
+        assert_eq!(redacted(&redactor, &format!("{exact}!")), "****!");
+    }
+}
@@ -0,0 +1,64 @@
+//! What the Status line shows: whether the terminal process exists and, while it does,
+//! whether its data feed is working, as one state with explicit transitions.
+
+use super::status::StatusReport;
+use super::theme::WARNING_ORANGE;
+use eframe::egui::Color32;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum RunState {
+    /// No process.
+    Stopped,
+    /// The process is up but the status endpoint hasn't been polled yet.
+    Starting,
+    /// Polled, but the feed has not been connected since the process started.
+    Connecting,
+    /// The status endpoint reports a live connection.
+    Healthy,
+    /// The feed was connected earlier in this run and no longer is.
+    Degraded,
+}
+
+impl Default for RunState {
+    fn default() -> Self {
+        Self::Stopped
+    }
+}
+
+impl RunState {
+    /// Advance from `self` given whether the process runs and the latest status report.
+    pub fn next(self, process_running: bool, report: Option<&StatusReport>) -> Self {
+        if !process_running {
+            return Self::Stopped;
+        }
+        let connected = report.map(StatusReport::is_connected);
+        match (self, connected) {
+            (Self::Stopped, _) | (Self::Starting, None) => Self::Starting,
+            (_, Some(true)) => Self::Healthy,
+            (Self::Starting | Self::Connecting, Some(false)) => Self::Connecting,
+            (Self::Healthy | Self::Degraded, Some(false)) => Self::Degraded,
+            // Reports are only dropped when the process stops, so keep the current state.
+            (state, None) => state,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Stopped => "Stopped",
+            Self::Starting => "Starting",
+            Self::Connecting => "Running (connecting)",
+            Self::Healthy => "Running (healthy)",
+            Self::Degraded => "Running (degraded)",
+        }
+    }
+
+    pub fn color(self) -> Color32 {
+        match self {
+            Self::Stopped => Color32::GRAY,
+            Self::Starting => Color32::LIGHT_BLUE,
+            Self::Connecting => Color32::YELLOW,
+            Self::Healthy => Color32::GREEN,
+            Self::Degraded => WARNING_ORANGE,
+        }
+    }
+}
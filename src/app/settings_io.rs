@@ -0,0 +1,61 @@
+//! Export and import of the app settings (`AppConfig`) as a standalone TOML file.
+
+use crate::model::AppConfig;
+use serde_json::Value;
+use std::path::Path;
+
+/// How much of a long value (e.g. notes) the import preview shows.
+const PREVIEW_CHARS: usize = 60;
+
+/// A setting whose value differs between the current and the imported settings.
+pub struct SettingChange {
+    pub field: String,
+    pub old_value: String,
+    pub new_value: String,
+}
+
+pub fn export(path: &Path, config: &AppConfig) -> Result<(), String> {
+    confy::store_path(path, config).map_err(|e| e.to_string())
+}
+
+pub fn import(path: &Path) -> Result<AppConfig, String> {
+    // confy writes defaults to a missing file, which an import must never do.
+    if !path.is_file() {
+        return Err(format!("{} does not exist", path.display()));
+    }
+    confy::load_path(path).map_err(|e| e.to_string())
+}
+
+/// Every top-level setting that `incoming` would change, sorted by name.
+pub fn diff(current: &AppConfig, incoming: &AppConfig) -> Vec<SettingChange> {
+    let (Ok(Value::Object(current)), Ok(Value::Object(incoming))) = (
+        serde_json::to_value(current),
+        serde_json::to_value(incoming),
+    ) else {
+        return Vec::new();
+    };
+    incoming
+        .iter()
+        .filter_map(|(field, new)| {
+            let old = current.get(field).unwrap_or(&Value::Null);
+            (old != new).then(|| SettingChange {
+                field: field.clone(),
+                old_value: preview(old),
+                new_value: preview(new),
+            })
+        })
+        .collect()
+}
+
+fn preview(value: &Value) -> String {
+    let text = match value {
+        Value::Null => "(none)".to_string(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    if text.chars().count() > PREVIEW_CHARS {
+        format!("{}…", text.chars().take(PREVIEW_CHARS).collect::<String>())
+    } else {
+        text
+    }
+}
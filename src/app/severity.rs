@@ -0,0 +1,45 @@
+//! Classifies terminal output lines by log level.
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+/// Best-effort level for one output line, from the level tokens the terminal and the JVM print.
+/// Matching ignores case.
+pub fn classify(line: &str) -> Severity {
+    let upper = line.trim_start().to_ascii_uppercase();
+    // Stack frames belong to an exception line that was already counted.
+    if upper.starts_with("AT ") || upper.starts_with("...") {
+        return Severity::Info;
+    }
+    if ["ERROR", "SEVERE", "FATAL"]
+        .iter()
+        .any(|level| upper.contains(level))
+        || upper.contains("EXCEPTION IN THREAD")
+        || upper.starts_with("CAUSED BY:")
+        || upper.contains("EXCEPTION:")
+    {
+        Severity::Error
+    } else if upper.contains("WARN") {
+        Severity::Warning
+    } else {
+        Severity::Info
+    }
+}
+
+/// Whether the line reports rejected credentials, which the terminal doesn't always log
+/// at error level.
+pub fn is_auth_failure(line: &str) -> bool {
+    let lower = line.to_ascii_lowercase();
+    [
+        "invalid credentials",
+        "authentication failed",
+        "login failed",
+        "unauthorized",
+    ]
+    .iter()
+    .any(|phrase| lower.contains(phrase))
+}
@@ -0,0 +1,212 @@
+//! Polls the terminal's REST API for its connection status, normally on localhost.
+//!
+//! The status endpoint has moved between terminal versions, so several candidate paths are
+//! tried and the one that answers is remembered and tried first on later polls. When a
+//! statistics path is configured, feed throughput is fetched on the same schedule.
+
+use super::theme::WARNING_ORANGE;
+use chrono::{DateTime, Local};
+use eframe::egui::Color32;
+use serde::Deserialize;
+use std::{
+    io::{Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{channel, Receiver},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Outcome of one poll.
+pub struct StatusReport {
+    /// The path that answered, or `None` if none of the candidates did.
+    pub path: Option<String>,
+    /// Response body (e.g. `CONNECTED`), or why no path answered.
+    pub detail: String,
+    pub checked_at: DateTime<Local>,
+    /// Feed statistics, or why they couldn't be read; `None` when no stats path is set.
+    pub stats: Option<Result<FeedStats, String>>,
+}
+
+impl StatusReport {
+    /// Whether the terminal reported a live upstream connection.
+    pub fn is_connected(&self) -> bool {
+        let detail = self.detail.to_ascii_uppercase();
+        self.path.is_some() && detail.contains("CONNECTED") && !detail.contains("DISCONNECTED")
+    }
+}
+
+/// Upstream connection as far as the status endpoint tells, for the Setup tab's dot.
+#[derive(PartialEq, Clone, Copy)]
+pub enum ConnectionStatus {
+    Connected,
+    Connecting,
+    Disconnected,
+    /// Nothing polled yet.
+    Unknown,
+}
+
+impl ConnectionStatus {
+    pub fn from_report(report: Option<&StatusReport>) -> Self {
+        let Some(report) = report else {
+            return Self::Unknown;
+        };
+        let detail = report.detail.to_ascii_uppercase();
+        if report.is_connected() {
+            Self::Connected
+        } else if report.path.is_some()
+            && ["CONNECTING", "UNVERIFIED"]
+                .iter()
+                .any(|word| detail.contains(word))
+        {
+            Self::Connecting
+        } else {
+            Self::Disconnected
+        }
+    }
+
+    pub fn color(self) -> Color32 {
+        match self {
+            Self::Connected => Color32::from_rgb(0, 180, 0),
+            Self::Connecting => WARNING_ORANGE,
+            Self::Disconnected => Color32::RED,
+            Self::Unknown => Color32::GRAY,
+        }
+    }
+}
+
+/// Throughput counters from the statistics endpoint. Field names vary between terminal
+/// versions, so the common spellings are accepted and anything missing stays `None`.
+#[derive(Deserialize, Clone, Copy)]
+pub struct FeedStats {
+    #[serde(
+        default,
+        alias = "messagesReceived",
+        alias = "total_messages",
+        alias = "msgs_received"
+    )]
+    pub messages_received: Option<u64>,
+    #[serde(
+        default,
+        alias = "queueDepth",
+        alias = "queue_size",
+        alias = "queueSize"
+    )]
+    pub queue_depth: Option<u64>,
+}
+
+pub struct StatusPoller {
+    rx: Receiver<StatusReport>,
+    stop: Arc<AtomicBool>,
+}
+
+impl StatusPoller {
+    pub fn start(host: String, port: u16, paths: Vec<String>, stats_path: Option<String>) -> Self {
+        let (tx, rx) = channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = Arc::clone(&stop);
+        thread::spawn(move || {
+            let mut paths = paths;
+            while !stopped.load(Ordering::Relaxed) {
+                let mut report = poll_once(&host, port, &mut paths);
+                if report.path.is_some() {
+                    report.stats = stats_path
+                        .as_deref()
+                        .map(|path| fetch_stats(&host, port, path));
+                }
+                if tx.send(report).is_err() {
+                    break;
+                }
+                thread::sleep(POLL_INTERVAL);
+            }
+        });
+        Self { rx, stop }
+    }
+
+    /// The newest report since the last call, if any.
+    pub fn latest(&self) -> Option<StatusReport> {
+        self.rx.try_iter().last()
+    }
+}
+
+impl Drop for StatusPoller {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Try each path until one returns 200, moving it to the front for the next poll.
+fn poll_once(host: &str, port: u16, paths: &mut Vec<String>) -> StatusReport {
+    let mut last_error = String::from("no status paths configured");
+    for index in 0..paths.len() {
+        match http_get(host, port, &paths[index]) {
+            Ok((200, body)) => {
+                let path = paths.remove(index);
+                paths.insert(0, path.clone());
+                return StatusReport {
+                    path: Some(path),
+                    detail: body.trim().to_string(),
+                    checked_at: Local::now(),
+                    stats: None,
+                };
+            }
+            Ok((code, _)) => last_error = format!("{} returned HTTP {code}", paths[index]),
+            Err(e) => last_error = format!("{}: {e}", paths[index]),
+        }
+    }
+    StatusReport {
+        path: None,
+        detail: last_error,
+        checked_at: Local::now(),
+        stats: None,
+    }
+}
+
+fn fetch_stats(host: &str, port: u16, path: &str) -> Result<FeedStats, String> {
+    match http_get(host, port, path) {
+        Ok((200, body)) => {
+            let stats: FeedStats = serde_json::from_str(body.trim())
+                .map_err(|e| format!("unexpected response from {path}: {e}"))?;
+            if stats.messages_received.is_none() && stats.queue_depth.is_none() {
+                return Err(format!("{path} reports no known counters"));
+            }
+            Ok(stats)
+        }
+        Ok((code, _)) => Err(format!("{path} returned HTTP {code}")),
+        Err(e) => Err(format!("{path}: {e}")),
+    }
+}
+
+/// Minimal HTTP/1.0 GET, returning the status code and body.
+fn http_get(host: &str, port: u16, path: &str) -> std::io::Result<(u16, String)> {
+    let addr = (host, port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "host did not resolve"))?;
+    let mut stream = TcpStream::connect_timeout(&addr, REQUEST_TIMEOUT)?;
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+    write!(
+        stream,
+        "GET {path} HTTP/1.0\r\nHost: {host}:{port}\r\nConnection: close\r\n\r\n"
+    )?;
+
+    let mut raw = Vec::new();
+    stream.read_to_end(&mut raw)?;
+    let response = String::from_utf8_lossy(&raw);
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .unwrap_or((response.as_ref(), ""));
+    let code = head
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse().ok())
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidData, "not an HTTP reply"))?;
+    Ok((code, body.to_string()))
+}
@@ -1,8 +1,30 @@
-use super::ThetaApp;
-use crate::model::Tab;
-use eframe::egui::text::{LayoutJob, TextFormat};
-use eframe::egui::{self, Color32, FontId, Galley, ScrollArea, TextEdit, Ui, Vec2};
+use super::alert;
+use super::ansi;
+use super::java::{jvm_profile_description, jvm_profile_label, JVM_PROFILES};
+use super::known_keys;
+use super::log_buffer::LogBuffer;
+use super::notifications::Level;
+use super::properties::{
+    describe_changes, duplicate_keys, group_by_region, losing_duplicate_lines, parse_entries,
+    region_names, schema_default, ConfigEntry,
+};
+use super::severity::{self, Severity};
+use super::status::ConnectionStatus;
+use super::theme::{config_palette, WARNING_ORANGE};
+use super::{
+    format_uptime, timestamp_format_is_valid, ConfigEncoding, LaunchAction, ThetaApp,
+    ACTION_MARKER_PREFIX,
+};
+use crate::model::{
+    CommandMacro, ConfigViewMode, DuplicateKeyPolicy, GuiCrashBehavior, JvmProfile, Tab, Theme,
+    DEFAULT_TIMESTAMP_FORMAT, FONT_SIZE_RANGE, MIN_WINDOW_FLOOR,
+};
+use eframe::egui::text::{CCursor, LayoutJob, TextFormat};
+use eframe::egui::text_edit::CCursorRange;
+use eframe::egui::{self, Color32, Galley, ScrollArea, TextBuffer, TextEdit, Ui, Vec2};
 use rfd::FileDialog;
+use std::ops::Range;
+use std::path::Path;
 use std::sync::Arc;
 
 //
@@ -11,18 +33,107 @@ use std::sync::Arc;
 // ────────────────────────────────────────────────────────────────────────────
 //
 pub fn show_setup_tab(app: &mut ThetaApp, ui: &mut Ui) {
+    if app.readiness.is_none() {
+        app.run_readiness_checks();
+    }
+    egui::CollapsingHeader::new("✔ Readiness")
+        .default_open(true)
+        .show(ui, |ui| {
+            let mut all_passed = true;
+            for check in app.readiness.iter().flatten() {
+                all_passed &= check.passed;
+                ui.horizontal_wrapped(|ui| {
+                    if check.passed {
+                        ui.colored_label(Color32::GREEN, "✔");
+                    } else {
+                        ui.colored_label(Color32::RED, "✖");
+                    }
+                    ui.strong(check.label);
+                    ui.label(&check.detail);
+                });
+                if !check.passed {
+                    ui.weak(check.hint);
+                }
+            }
+            ui.horizontal(|ui| {
+                if ui.button("Re-check").clicked() {
+                    app.refresh_java_check();
+                    app.run_readiness_checks();
+                }
+                if all_passed {
+                    ui.colored_label(Color32::GREEN, "Ready to start.");
+                }
+            });
+        });
+
+    ui.add_space(8.0);
+
     egui::CollapsingHeader::new("⌨ Login Credentials")
         .default_open(true)
         .show(ui, |ui| {
-            if app.credentials_saved {
+            ui.horizontal(|ui| {
+                ui.label("Profile:");
+                let mut selected = app.profile.clone();
+                egui::ComboBox::from_id_source("credential_profile")
+                    .selected_text(if selected.is_empty() {
+                        "Default"
+                    } else {
+                        selected.as_str()
+                    })
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut selected, String::new(), "Default");
+                        for profile in &app.profiles {
+                            ui.selectable_value(&mut selected, profile.clone(), profile);
+                        }
+                    })
+                    .response
+                    .on_hover_text("Each profile keeps its own username and password.");
+                app.select_profile(selected);
+                if !app.profile.is_empty() && ui.button("Delete profile").clicked() {
+                    app.delete_profile();
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.add(
+                    TextEdit::singleline(&mut app.new_profile_name)
+                        .hint_text("New profile name")
+                        .desired_width(160.0),
+                );
+                let name = app.new_profile_name.trim();
+                let can_add = !name.is_empty() && !app.profiles.iter().any(|p| p == name);
+                if ui
+                    .add_enabled(can_add, egui::Button::new("Add profile"))
+                    .clicked()
+                {
+                    app.add_profile();
+                }
+            });
+            ui.add_space(4.0);
+            if app.credentials_saved && !app.editing_credentials {
                 ui.horizontal(|ui| {
                     ui.label("Username (saved):");
                     ui.monospace(&app.username_input);
+                    if ui.small_button("Edit").clicked() {
+                        app.editing_credentials = true;
+                        app.password_input.clear();
+                    }
                 });
                 ui.label("Password stored in keychain.");
-                if ui.button("Remove all credentials").clicked() {
-                    app.remove_credentials();
-                }
+                ui.checkbox(
+                    &mut app.cache_credentials_in_memory,
+                    "Keep credentials in memory for this session",
+                )
+                .on_hover_text(
+                    "Read the keychain once per session instead of on every start, which can \
+                     stop repeated macOS prompts. The copy lives in this app's memory until it \
+                     exits (then it is wiped); the keychain stays the only place on disk.",
+                );
+                ui.horizontal_wrapped(|ui| {
+                    if ui.button("Remove all credentials").clicked() {
+                        app.remove_credentials();
+                    }
+                    login_test_row(app, ui);
+                });
             } else {
                 ui.horizontal(|ui| {
                     ui.label("Username:");
@@ -33,15 +144,57 @@ pub fn show_setup_tab(app: &mut ThetaApp, ui: &mut Ui) {
                 });
                 ui.horizontal(|ui| {
                     ui.label("Password:");
+                    let hint = if app.editing_credentials {
+                        "Leave blank to keep the saved password"
+                    } else {
+                        ""
+                    };
                     ui.add(
                         TextEdit::singleline(&mut app.password_input)
-                            .password(true)
-                            .desired_width(ui.available_width() - 8.0),
+                            .password(!app.show_password)
+                            .hint_text(hint)
+                            .desired_width(ui.available_width() - 40.0),
                     );
+                    let hint = if app.show_password {
+                        "Hide password"
+                    } else {
+                        "Show password"
+                    };
+                    if ui
+                        .selectable_label(app.show_password, "👁")
+                        .on_hover_text(hint)
+                        .clicked()
+                    {
+                        app.show_password = !app.show_password;
+                    }
+                });
+                ui.horizontal_wrapped(|ui| {
+                    if ui
+                        .add_enabled(
+                            app.credential_inputs_complete(),
+                            egui::Button::new("Save Credentials"),
+                        )
+                        .on_disabled_hover_text("Enter a username and password")
+                        .clicked()
+                    {
+                        app.save_credentials();
+                    }
+                    if app.editing_credentials && ui.button("Cancel").clicked() {
+                        app.cancel_credential_edit();
+                    }
+                    login_test_row(app, ui);
                 });
-                if ui.button("Save Credentials").clicked() {
-                    app.save_credentials();
-                }
+            }
+            if cfg!(target_os = "macos")
+                && ui
+                    .button("Test keychain access")
+                    .on_hover_text(
+                        "Write, read back and delete a dummy keychain entry, to tell a missing \
+                         entitlement or permission apart from wrong credentials",
+                    )
+                    .clicked()
+            {
+                app.test_keychain();
             }
         });
 
@@ -54,10 +207,7 @@ pub fn show_setup_tab(app: &mut ThetaApp, ui: &mut Ui) {
                 ui.label("ThetaTerminal.jar Path:");
             });
             ui.horizontal(|ui| {
-                ui.add(
-                    TextEdit::singleline(&mut app.jar_path)
-                        .desired_width(ui.available_width() - 60.0),
-                );
+                path_field(ui, &mut app.jar_path, app.show_path_basenames);
                 if ui.button("Browse").clicked() {
                     if let Some(file) = FileDialog::new()
                         .add_filter("JAR Files", &["jar"])
@@ -67,10 +217,108 @@ pub fn show_setup_tab(app: &mut ThetaApp, ui: &mut Ui) {
                     }
                 }
             });
+            ui.horizontal_wrapped(|ui| {
+                let testing = app.jar_test.is_some();
+                if ui
+                    .add_enabled(!testing, egui::Button::new("Test jar"))
+                    .on_hover_text("Run the jar with --version to check it works with this Java")
+                    .clicked()
+                {
+                    app.test_jar();
+                }
+                if testing {
+                    ui.spinner();
+                } else {
+                    match &app.jar_test_result {
+                        Some(Ok(version)) => {
+                            ui.colored_label(Color32::from_rgb(0, 180, 0), version);
+                        }
+                        Some(Err(e)) => {
+                            ui.colored_label(Color32::RED, e);
+                        }
+                        None => {}
+                    }
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label("Java Executable:");
+            });
+            ui.horizontal(|ui| {
+                ui.add(
+                    TextEdit::singleline(&mut app.java_path)
+                        .hint_text("java from PATH")
+                        .desired_width(ui.available_width() - 60.0),
+                )
+                .on_hover_text(
+                    "Leave empty to use java (javaw on Windows) from PATH. On Windows, pick \
+                     javaw.exe to avoid a console window.",
+                );
+                if ui.button("Browse").clicked() {
+                    if let Some(file) = FileDialog::new().pick_file() {
+                        app.java_path = file.to_string_lossy().to_string();
+                        // The version shown below was for the previous executable.
+                        app.readiness = None;
+                    }
+                }
+            });
+            if let Some(Ok(version)) = app.java_version() {
+                ui.weak(format!("Java: {version}"));
+            }
             ui.checkbox(
                 &mut app.auto_start,
                 "Start ThetaData Terminal on app launch",
             );
+            ui.checkbox(
+                &mut app.capture_output_to_file,
+                "Write terminal output to a file (faster under heavy load)",
+            )
+            .on_hover_text(
+                "The Terminal tab then refreshes once per second. Takes effect on the next start.",
+            );
+            ui.horizontal(|ui| {
+                ui.label("JVM profile:");
+                egui::ComboBox::from_id_source("jvm_profile")
+                    .selected_text(jvm_profile_label(app.jvm_profile))
+                    .show_ui(ui, |ui| {
+                        for profile in JVM_PROFILES {
+                            ui.selectable_value(
+                                &mut app.jvm_profile,
+                                profile,
+                                jvm_profile_label(profile),
+                            );
+                        }
+                    });
+            });
+            ui.weak(jvm_profile_description(app.jvm_profile));
+            if app.jvm_profile == JvmProfile::Custom {
+                ui.add(
+                    TextEdit::singleline(&mut app.jvm_args)
+                        .hint_text("-Xmx2g -Dhttp.proxyHost=proxy.local")
+                        .desired_width(ui.available_width() - 8.0),
+                )
+                .on_hover_text("Quote values containing spaces, e.g. -Dapp.name=\"Theta Data\"");
+            }
+            ui.horizontal(|ui| {
+                let zone = app.system_timezone.as_deref().unwrap_or("not detected");
+                ui.checkbox(
+                    &mut app.pass_system_timezone,
+                    format!("Pass system timezone to Java ({zone})"),
+                )
+                .on_hover_text(
+                    "Adds -Duser.timezone so the terminal's timestamps match this machine. \
+                     Skipped if the JVM arguments already set it. Takes effect on the next start.",
+                );
+                if app.pass_system_timezone && app.system_timezone.is_none() {
+                    ui.colored_label(
+                        WARNING_ORANGE,
+                        "⚠ Not passed: no named zone found for this machine",
+                    )
+                    .on_hover_text(
+                        "Java falls back to its own detection. Set -Duser.timezone in the JVM \
+                         arguments to choose one.",
+                    );
+                }
+            });
         });
 
     ui.add_space(8.0);
@@ -78,25 +326,229 @@ pub fn show_setup_tab(app: &mut ThetaApp, ui: &mut Ui) {
     egui::CollapsingHeader::new("☑ Terminal Controls")
         .default_open(true)
         .show(ui, |ui| {
+            let can_launch = app.credentials_saved;
             ui.horizontal(|ui| {
-                if ui.button("Start").clicked() {
-                    app.start_terminal();
+                let launch_hint = "Save credentials above to start the terminal";
+                if ui
+                    .add_enabled(can_launch, egui::Button::new("Start"))
+                    .on_hover_text("Ctrl+S")
+                    .on_disabled_hover_text(launch_hint)
+                    .clicked()
+                {
+                    app.mark_user_action("Start");
+                    app.request_launch(LaunchAction::Start);
                 }
-                if ui.button("Stop").clicked() {
+                let stop_label = if app.stop_deadline.is_some() {
+                    "Force Stop"
+                } else {
+                    "Stop"
+                };
+                if ui.button(stop_label).on_hover_text("Ctrl+K").clicked() {
+                    app.mark_user_action("Stop");
                     app.stop_terminal();
                 }
-                if ui.button("Reset").clicked() {
-                    app.reset_terminal();
+                if ui
+                    .add_enabled(can_launch, egui::Button::new("Reset"))
+                    .on_hover_text("Ctrl+R")
+                    .on_disabled_hover_text(launch_hint)
+                    .clicked()
+                {
+                    app.mark_user_action("Reset");
+                    app.request_reset();
+                }
+                if ui
+                    .add_enabled(
+                        can_launch && app.skip_restart_confirmation,
+                        egui::Button::new("⟳"),
+                    )
+                    .on_hover_text("Quick restart, no confirmation")
+                    .on_disabled_hover_text(
+                        "Opt into \"Skip restart confirmation\" to enable quick restart",
+                    )
+                    .clicked()
+                {
+                    app.mark_user_action("Quick restart");
+                    app.quick_restart();
+                }
+                ui.checkbox(
+                    &mut app.skip_restart_confirmation,
+                    "Skip restart confirmation",
+                )
+                .on_hover_text("For this session only");
+            });
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut app.auto_restart, "Restart automatically if it crashes")
+                    .on_hover_text(
+                        "After a failed exit (not Stop), start again with a growing delay; \
+                         gives up after 5 crashes in a row",
+                    );
+                if let Some(at) = app.auto_restart_at {
+                    let left = at.saturating_duration_since(std::time::Instant::now());
+                    ui.weak(format!("restarting in {}s", left.as_secs() + 1));
+                    if ui.small_button("Cancel").clicked() {
+                        app.auto_restart_at = None;
+                    }
                 }
             });
+            ui.horizontal(|ui| {
+                ui.label("If this app crashes:");
+                egui::ComboBox::from_id_source("on_gui_crash")
+                    .selected_text(match app.on_gui_crash {
+                        GuiCrashBehavior::LeaveRunning => "Leave the terminal running",
+                        GuiCrashBehavior::StopTerminal => "Stop the terminal",
+                    })
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(
+                            &mut app.on_gui_crash,
+                            GuiCrashBehavior::LeaveRunning,
+                            "Leave the terminal running",
+                        );
+                        ui.selectable_value(
+                            &mut app.on_gui_crash,
+                            GuiCrashBehavior::StopTerminal,
+                            "Stop the terminal",
+                        );
+                    })
+                    .response
+                    .on_hover_text(
+                        "Closing the app normally always stops the terminal. Takes effect on \
+                         the next start.",
+                    );
+            });
+            ui.horizontal(|ui| {
+                ui.label("Stop grace period:");
+                ui.add(
+                    egui::DragValue::new(&mut app.shutdown_grace_ms)
+                        .clamp_range(0..=60_000)
+                        .speed(100)
+                        .suffix(" ms"),
+                )
+                .on_hover_text(
+                    "Stop asks the terminal to exit and kills it only after this long; \
+                     0 kills it right away",
+                );
+            });
+            if !can_launch {
+                ui.colored_label(WARNING_ORANGE, "👁 Monitoring only — no credentials")
+                    .on_hover_text(
+                        "Without saved credentials this app can't start a terminal, but it can \
+                     watch one someone else started, here or on another machine.",
+                    );
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut app.monitoring, "Monitor terminal at");
+                    ui.add_enabled(
+                        !app.monitoring,
+                        TextEdit::singleline(&mut app.monitor_host).desired_width(120.0),
+                    );
+                    ui.label(":");
+                    ui.add_enabled(
+                        !app.monitoring,
+                        egui::DragValue::new(&mut app.monitor_port).clamp_range(1..=65535),
+                    );
+                });
+            }
+            let monitored = app.process.is_none() && app.monitoring && !can_launch;
             ui.horizontal(|ui| {
                 ui.label("Status:");
                 if app.process.is_some() {
-                    ui.strong("Running");
+                    ui.colored_label(app.run_state.color(), app.run_state.label());
+                    let details: Vec<String> =
+                        app.process_pid
+                            .map(|pid| format!("PID {pid}"))
+                            .into_iter()
+                            .chain(app.started_at.map(|started| {
+                                format!("uptime {}", format_uptime(started.elapsed()))
+                            }))
+                            .collect();
+                    if !details.is_empty() {
+                        ui.label(format!("({})", details.join(", ")));
+                    }
+                } else if monitored {
+                    ui.strong(format!(
+                        "Monitoring {}:{}",
+                        app.monitor_host.trim(),
+                        app.monitor_port
+                    ));
+                } else if let Some(code) = app.last_exit_code {
+                    ui.strong(format!("Stopped (exit code {code})"));
                 } else {
                     ui.strong("Stopped");
                 }
             });
+            if app.process.is_some() || monitored {
+                ui.horizontal_wrapped(|ui| {
+                    ui.label("Server:");
+                    let connection = ConnectionStatus::from_report(app.terminal_status.as_ref());
+                    ui.colored_label(connection.color(), "●");
+                    match &app.terminal_status {
+                        None => {
+                            ui.weak("checking…");
+                        }
+                        Some(report) => {
+                            let checked = report.checked_at.format("%H:%M:%S");
+                            match &report.path {
+                                Some(path) => {
+                                    ui.strong(&report.detail)
+                                        .on_hover_text(format!("via {path}, checked {checked}"));
+                                }
+                                None => {
+                                    ui.colored_label(Color32::RED, "Disconnected")
+                                        .on_hover_text(format!(
+                                            "No status path answered ({}), checked {checked}",
+                                            report.detail
+                                        ));
+                                }
+                            }
+                        }
+                    }
+                });
+            }
+            if app.process.is_some() || monitored {
+                feed_statistics(app, ui);
+            }
+            ui.horizontal(|ui| {
+                if ui
+                    .button("Check port")
+                    .on_hover_text("Find out which process is listening on the REST port")
+                    .clicked()
+                {
+                    app.diagnose_port_conflict();
+                }
+            });
+            if let Some(holder) = app.port_holder.clone() {
+                ui.horizontal_wrapped(|ui| {
+                    ui.colored_label(Color32::YELLOW, holder.describe());
+                    if ui.small_button("Copy").clicked() {
+                        ui.output_mut(|o| o.copied_text = holder.describe());
+                    }
+                    if holder.is_theta_terminal() && ui.small_button("Kill…").clicked() {
+                        app.confirm_kill_port_holder = true;
+                    }
+                    if ui.small_button("Dismiss").clicked() {
+                        app.port_holder = None;
+                    }
+                });
+                if app.confirm_kill_port_holder {
+                    egui::Window::new("Kill process?")
+                        .collapsible(false)
+                        .resizable(false)
+                        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                        .show(ui.ctx(), |ui| {
+                            ui.label(format!(
+                                "Kill {} (PID {}) to free port {}?",
+                                holder.name, holder.pid, holder.port
+                            ));
+                            ui.horizontal(|ui| {
+                                if ui.button("Kill").clicked() {
+                                    app.kill_port_holder();
+                                }
+                                if ui.button("Cancel").clicked() {
+                                    app.confirm_kill_port_holder = false;
+                                }
+                            });
+                        });
+                }
+            }
         });
 
     ui.add_space(8.0);
@@ -118,158 +570,1924 @@ pub fn show_setup_tab(app: &mut ThetaApp, ui: &mut Ui) {
                         ui.selectable_value(&mut app.default_tab, Tab::Config, "Config");
                     });
             });
-        });
-
-    ui.add_space(16.0);
-}
-
-//
-// ────────────────────────────────────────────────────────────────────────────
-//   :: Tab 2: Terminal
-// ────────────────────────────────────────────────────────────────────────────
-//
-pub fn show_terminal_tab(app: &mut ThetaApp, ui: &mut Ui) {
-    if ui.button("Copy Output").clicked() {
-        ui.output_mut(|o| o.copied_text = app.log_text.clone());
-    }
-    ui.add_space(4.0);
-
-    // Make the terminal output fill all remaining height
-    let available = ui.available_size();
-    // Auto-scroll region
-    ScrollArea::vertical().stick_to_bottom(true).show(ui, |ui| {
-        let mut display_buffer = app.log_text.clone();
-        ui.add_sized(
-            available,
-            TextEdit::multiline(&mut display_buffer)
-                .font(egui::TextStyle::Monospace)
-                .lock_focus(true)
-                .desired_rows(10)
-                .desired_width(f32::INFINITY)
-                .margin(Vec2::new(0.0, 4.0))
-                .interactive(true),
-        );
-    });
-}
-
-//
-// ────────────────────────────────────────────────────────────────────────────
-//   :: Tab 3: Config (Syntax-Highlighted Editor with Refresh)
-// ────────────────────────────────────────────────────────────────────────────
-//
-pub fn show_config_tab(app: &mut ThetaApp, ui: &mut Ui) {
-    egui::CollapsingHeader::new("ThetaData Config File")
-        .default_open(true)
-        .show(ui, |ui| {
-            // 1) The path input
             ui.horizontal(|ui| {
-                ui.label("ThetaData Config File Path:");
+                ui.label("Theme:");
+                egui::ComboBox::from_id_source("theme")
+                    .selected_text(match app.theme {
+                        Theme::Dark => "Dark",
+                        Theme::Light => "Light",
+                        Theme::HighContrast => "High contrast",
+                    })
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut app.theme, Theme::Dark, "Dark");
+                        ui.selectable_value(&mut app.theme, Theme::Light, "Light");
+                        ui.selectable_value(&mut app.theme, Theme::HighContrast, "High contrast");
+                    });
             });
             ui.horizontal(|ui| {
+                ui.label("Log and config font size:");
                 ui.add(
-                    TextEdit::singleline(&mut app.thetadata_config_path)
-                        .desired_width(ui.available_width() - 60.0),
+                    egui::Slider::new(&mut app.font_size, FONT_SIZE_RANGE.0..=FONT_SIZE_RANGE.1)
+                        .step_by(1.0)
+                        .suffix(" pt"),
                 );
-                if ui.button("Browse").clicked() {
-                    if let Some(file) = FileDialog::new().pick_file() {
-                        app.thetadata_config_path = file.to_string_lossy().to_string();
-                        if let Ok(text) =
-                            super::ThetaApp::read_thetadata_config_file(&app.thetadata_config_path)
-                        {
-                            app.thetadata_config_text = text;
-                            app.append_log("Config file loaded from browse.\n");
-                        } else {
-                            app.append_log("Failed to load config from browse.\n");
-                        }
+            });
+            ui.horizontal_wrapped(|ui| {
+                ui.label("Minimum window size:");
+                ui.add(
+                    egui::DragValue::new(&mut app.min_window_width)
+                        .clamp_range(MIN_WINDOW_FLOOR.0..=4000.0)
+                        .suffix(" px"),
+                );
+                ui.label("×");
+                ui.add(
+                    egui::DragValue::new(&mut app.min_window_height)
+                        .clamp_range(MIN_WINDOW_FLOOR.1..=4000.0)
+                        .suffix(" px"),
+                );
+                ui.weak("(applies on next launch)");
+            });
+            ui.checkbox(
+                &mut app.save_panel_on_all_tabs,
+                "Show the config Save bar on every tab while there are unsaved changes",
+            );
+            ui.checkbox(
+                &mut app.confirm_quit_while_running,
+                "Ask before quitting while the terminal is running",
+            );
+            ui.checkbox(
+                &mut app.minimize_to_tray,
+                "Hide to the system tray when closed while the terminal is running",
+            )
+            .on_hover_text(
+                "Quit from the tray menu to exit. Windows and macOS only; on macOS the window \
+                 is minimized to the Dock instead of hidden.",
+            );
+            ui.checkbox(
+                &mut app.show_path_basenames,
+                "Show only file names for the jar and config paths",
+            )
+            .on_hover_text("The full path is shown on hover; turn this off to edit it");
+            ui.horizontal(|ui| {
+                ui.label("Terminal REST port:");
+                ui.add(egui::DragValue::new(&mut app.rest_port).clamp_range(1..=65535))
+                    .on_hover_text(
+                        "Where the status poller and port checks look for the terminal you \
+                         start; must match HTTP_PORT in its config",
+                    );
+            });
+            let config_port = app.config_http_port();
+            if let Some(port) = config_port.filter(|port| *port != app.rest_port) {
+                ui.horizontal_wrapped(|ui| {
+                    ui.colored_label(
+                        WARNING_ORANGE,
+                        format!("⚠ The loaded config sets HTTP_PORT={port}."),
+                    );
+                    if ui.small_button(format!("Use {port}")).clicked() {
+                        app.rest_port = port;
                     }
+                });
+            }
+            ui.label("Status endpoint paths (tried in order, one per line):")
+                .on_hover_text(
+                    "The path has changed between terminal versions; the first one that \
+                     answers is used. Applies the next time the terminal starts.",
+                );
+            ui.add(
+                TextEdit::multiline(&mut app.status_paths_text)
+                    .desired_rows(2)
+                    .desired_width(ui.available_width() - 8.0),
+            );
+            ui.horizontal(|ui| {
+                ui.label("Feed statistics path:");
+                ui.add(
+                    TextEdit::singleline(&mut app.stats_path)
+                        .hint_text("empty to turn off")
+                        .desired_width(ui.available_width() - 8.0),
+                );
+            });
+            ui.horizontal(|ui| {
+                ui.label("External editor:");
+                ui.add(
+                    TextEdit::singleline(&mut app.external_editor_command)
+                        .hint_text("OS default (e.g. code --wait)")
+                        .desired_width(ui.available_width() - 8.0),
+                );
+            });
+            ui.horizontal(|ui| {
+                ui.label("Sample output above:");
+                ui.add(
+                    egui::DragValue::new(&mut app.flood_threshold)
+                        .clamp_range(100..=1_000_000)
+                        .speed(50)
+                        .suffix(" lines/s"),
+                )
+                .on_hover_text(
+                    "When output stays above this rate, the Terminal tab shows only a sample \
+                     to stay responsive. Alerts and the log file still get every line.",
+                );
+            });
+            ui.checkbox(
+                &mut app.mark_user_actions,
+                "Mark Start/Stop/Reset/Save clicks in the log",
+            );
+            ui.horizontal(|ui| {
+                ui.label("Keep on screen:");
+                ui.add(
+                    egui::DragValue::new(&mut app.max_log_lines)
+                        .clamp_range(1000..=1_000_000)
+                        .speed(100)
+                        .suffix(" lines"),
+                )
+                .on_hover_text(
+                    "Older lines are dropped from the Terminal tab to keep memory and \
+                     rendering in check. The log file still gets every line.",
+                );
+            });
+            ui.horizontal(|ui| {
+                ui.checkbox(
+                    &mut app.alert_sound_enabled,
+                    "Play a sound on errors and disconnects",
+                )
+                .on_hover_text(
+                    "Errors, failed logins, a dropped connection or the terminal crashing. \
+                     Repeats within 10 seconds are skipped.",
+                );
+                ui.add_enabled(
+                    app.alert_sound_enabled,
+                    egui::Slider::new(&mut app.alert_volume, 0.0..=1.0).text("volume"),
+                )
+                .on_hover_text("Windows plays the alert at the system volume");
+                if ui
+                    .add_enabled(app.alert_sound_enabled, egui::Button::new("Test"))
+                    .clicked()
+                {
+                    alert::play(app.alert_volume);
                 }
             });
-
-            // 2) "Get from Terminal" button
+            ui.checkbox(
+                &mut app.notify_on_exit,
+                "Show a desktop notification when the terminal exits unexpectedly",
+            )
+            .on_hover_text("Stop and Reset don't trigger it");
+            ui.checkbox(
+                &mut app.confirm_start_with_unsaved_config,
+                "Ask to save unsaved config changes on Start or Reset",
+            );
+            ui.checkbox(
+                &mut app.check_for_updates,
+                "Check for app updates once a day",
+            )
+            .on_hover_text("Asks GitHub for the latest release; nothing is sent while off");
             ui.horizontal(|ui| {
-                if ui.button("Get from Terminal").clicked() {
-                    if let Some(detected) = &app.last_detected_config_path {
-                        app.thetadata_config_path = detected.clone();
-                        match super::ThetaApp::read_thetadata_config_file(detected) {
-                            Ok(contents) => {
-                                app.thetadata_config_text = contents;
-                                app.append_log("Config file loaded from terminal detection.\n");
-                            }
-                            Err(e) => {
-                                app.append_log(&format!("Failed to load config file: {e}\n"));
-                            }
-                        }
-                    } else {
-                        app.append_log("No config path detected yet. Launch the terminal first.\n");
+                if ui.button("Export settings…").clicked() {
+                    if let Some(file) = FileDialog::new()
+                        .add_filter("TOML", &["toml"])
+                        .set_file_name("thetadata_gui_settings.toml")
+                        .save_file()
+                    {
+                        app.export_settings(&file);
                     }
                 }
-
-                // 3) NEW: Refresh button
-                if ui.button("Refresh").clicked() {
-                    if app.thetadata_config_path.is_empty() {
-                        app.append_log("No config path set to refresh.\n");
-                    } else {
-                        match super::ThetaApp::read_thetadata_config_file(
-                            &app.thetadata_config_path,
-                        ) {
-                            Ok(text) => {
-                                app.thetadata_config_text = text;
-                                app.append_log("Config file refreshed from disk.\n");
-                            }
-                            Err(e) => app.append_log(&format!("Failed to refresh config: {e}\n")),
-                        }
+                if ui
+                    .button("Import settings…")
+                    .on_hover_text("Shows what would change before anything is applied")
+                    .clicked()
+                {
+                    if let Some(file) = FileDialog::new().add_filter("TOML", &["toml"]).pick_file()
+                    {
+                        app.import_settings(&file);
                     }
                 }
             });
-
-            ui.add_space(8.0);
-            ui.label("Edit your config file below (with minimal syntax highlighting):");
-
-            // Show the config file in a syntax-highlighted code editor
-            syntax_highlight_editor(ui, &mut app.thetadata_config_text);
-
-            ui.add_space(16.0);
-            ui.label("Remember to click 'Save' at the bottom to persist changes.");
+            ui.label("Notes:");
+            ui.add(
+                TextEdit::multiline(&mut app.notes)
+                    .hint_text("Account quirks, entitlements, expiry dates…")
+                    .desired_rows(3)
+                    .desired_width(ui.available_width() - 8.0),
+            );
         });
-}
-
-/// A code editor that highlights lines starting with '#' as comments, and everything else in green.
-/// Using `split_inclusive('\n')` so edits occur at the correct position.
-fn syntax_highlight_editor(ui: &mut Ui, text: &mut String) {
-    let mut layouter_fn =
-        move |ui: &egui::Ui, code: &str, _wrap_width: f32| highlight_config_text(ui, code);
-
-    ui.add(
-        TextEdit::multiline(text)
-            .font(egui::TextStyle::Monospace)
-            .desired_rows(15)
-            .desired_width(ui.available_width())
-            .lock_focus(false)
-            .layouter(&mut layouter_fn),
-    );
-}
-
-/// Minimal syntax highlighter:
-/// - Lines starting with '#' -> gray comment
-/// - Everything else -> pale green
-fn highlight_config_text(ui: &egui::Ui, code: &str) -> Arc<Galley> {
-    let mut job = LayoutJob::default();
-
-    for chunk in code.split_inclusive('\n') {
-        let is_comment = chunk.trim_start().starts_with('#');
-        let color = if is_comment {
-            Color32::LIGHT_GRAY
-        } else {
-            Color32::from_rgb(150, 255, 150)
-        };
 
-        let format = TextFormat {
-            font_id: FontId::monospace(14.0),
-            color,
-            ..Default::default()
-        };
-        job.append(chunk, 0.0, format);
-    }
+    ui.add_space(8.0);
 
-    ui.fonts(|fonts| fonts.layout_job(job))
+    egui::CollapsingHeader::new("🗎 Log File")
+        .default_open(false)
+        .show(ui, |ui| {
+            ui.checkbox(
+                &mut app.log_to_file,
+                "Write the log to a file (one per day)",
+            );
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut app.log_file_wrap, "Hard-wrap long lines at column");
+                ui.add_enabled(
+                    app.log_file_wrap,
+                    egui::DragValue::new(&mut app.log_file_wrap_width).clamp_range(40..=1000),
+                );
+            });
+            ui.weak("Wrapped rows start with ↪. The on-screen log is never wrapped this way.");
+            ui.checkbox(
+                &mut app.strip_ansi_in_saved_log,
+                "Strip ANSI color codes when using Save Log…",
+            );
+            ui.horizontal(|ui| {
+                ui.label("Timestamp format:");
+                ui.add(
+                    TextEdit::singleline(&mut app.log_timestamp_format)
+                        .hint_text(DEFAULT_TIMESTAMP_FORMAT)
+                        .desired_width(160.0),
+                )
+                .on_hover_text(
+                    "chrono strftime syntax, e.g. %Y-%m-%dT%H:%M:%S%.3f%:z for ISO 8601. \
+                     Used for the Timestamps option on the Terminal tab and in the log file.",
+                );
+                ui.checkbox(&mut app.log_timestamp_utc, "UTC");
+            });
+            if timestamp_format_is_valid(&app.log_timestamp_format) {
+                ui.weak(format!(
+                    "Example: {}",
+                    app.format_timestamp(chrono::Local::now())
+                ));
+            } else {
+                ui.colored_label(
+                    Color32::RED,
+                    format!("Invalid format; using {DEFAULT_TIMESTAMP_FORMAT}."),
+                );
+            }
+            if ui
+                .button("Truncate log file…")
+                .on_hover_text("Empty today's log file; the Terminal tab keeps its output")
+                .clicked()
+            {
+                app.confirm_truncate_log = true;
+            }
+            if app.confirm_truncate_log {
+                egui::Window::new("Truncate log file?")
+                    .collapsible(false)
+                    .resizable(false)
+                    .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                    .show(ui.ctx(), |ui| {
+                        ui.label(
+                            "Today's log file will be emptied. This can't be undone; \
+                             the on-screen log is not affected.",
+                        );
+                        ui.horizontal(|ui| {
+                            if ui.button("Truncate").clicked() {
+                                app.confirm_truncate_log = false;
+                                app.truncate_log_file();
+                            }
+                            if ui.button("Cancel").clicked() {
+                                app.confirm_truncate_log = false;
+                            }
+                        });
+                    });
+            }
+            if let Some(file_log) = &app.file_log {
+                ui.label(format!("Writing to {}", file_log.current_path().display()));
+            } else if let Some(err) = &app.file_log_error {
+                ui.colored_label(Color32::RED, format!("Log file unavailable: {err}"));
+            }
+        });
+
+    ui.add_space(8.0);
+
+    egui::CollapsingHeader::new("📡 Remote Log Tail")
+        .default_open(false)
+        .show(ui, |ui| {
+            ui.checkbox(&mut app.log_mirror_enabled, "Mirror the log over TCP");
+            ui.horizontal(|ui| {
+                ui.label("Port:");
+                ui.add(egui::DragValue::new(&mut app.log_mirror_port).clamp_range(1024..=65535));
+            });
+            ui.checkbox(
+                &mut app.log_mirror_all_interfaces,
+                "Listen on all interfaces (not just localhost)",
+            );
+            if app.log_mirror_all_interfaces {
+                ui.colored_label(
+                    Color32::RED,
+                    "Anyone who can reach this machine can read the log, unauthenticated \
+                     and unencrypted. Only enable this on a trusted network.",
+                );
+            }
+            if let Some(mirror) = &app.log_mirror {
+                ui.label(format!(
+                    "Listening on {} ({} client(s)). Connect with e.g. `nc <host> {}`.",
+                    mirror.addr,
+                    mirror.client_count(),
+                    mirror.addr.port()
+                ));
+            } else if let Some(err) = &app.log_mirror_error {
+                ui.colored_label(Color32::RED, format!("Not listening: {err}"));
+            }
+        });
+
+    ui.add_space(8.0);
+
+    egui::CollapsingHeader::new("⌘ Macros")
+        .default_open(false)
+        .show(ui, |ui| {
+            ui.label("Each line is sent to the terminal's stdin in order.");
+            let mut remove = None;
+            for (index, command_macro) in app.macros.iter_mut().enumerate() {
+                ui.push_id(index, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Name:");
+                        ui.add(
+                            TextEdit::singleline(&mut command_macro.name)
+                                .desired_width(ui.available_width() - 70.0),
+                        );
+                        if ui.button("Remove").clicked() {
+                            remove = Some(index);
+                        }
+                    });
+                    ui.add(
+                        TextEdit::multiline(&mut command_macro.commands)
+                            .font(egui::TextStyle::Monospace)
+                            .desired_rows(2)
+                            .desired_width(f32::INFINITY),
+                    );
+                });
+                ui.add_space(4.0);
+            }
+            if let Some(index) = remove {
+                app.macros.remove(index);
+            }
+            if ui.button("Add macro").clicked() {
+                app.macros.push(CommandMacro {
+                    name: format!("Macro {}", app.macros.len() + 1),
+                    commands: String::new(),
+                });
+            }
+        });
+
+    ui.add_space(16.0);
+}
+
+/// Message rate and queue depth from the statistics endpoint, with small plots of the
+/// recent history. Terminals without the endpoint get a one-line note instead.
+fn feed_statistics(app: &ThetaApp, ui: &mut Ui) {
+    let Some(Some(latest)) = app.terminal_status.as_ref().map(|r| r.stats.as_ref()) else {
+        return; // statistics are off, or the terminal hasn't answered yet
+    };
+    if let (Err(e), true) = (latest, app.feed_history.is_empty()) {
+        ui.weak(format!(
+            "Feed statistics aren't available from this terminal ({e})."
+        ));
+        return;
+    }
+    egui::CollapsingHeader::new("📈 Feed statistics")
+        .default_open(false)
+        .show(ui, |ui| {
+            let rates: Vec<Option<f64>> = app.feed_history.iter().map(|s| s.message_rate).collect();
+            let depths: Vec<Option<f64>> = app.feed_history.iter().map(|s| s.queue_depth).collect();
+            let last = |values: &[Option<f64>]| {
+                values
+                    .last()
+                    .copied()
+                    .flatten()
+                    .map_or("—".to_string(), |v| format!("{v:.0}"))
+            };
+            ui.label(format!("Messages/s: {}", last(&rates)));
+            sparkline(ui, &rates, Color32::from_rgb(80, 160, 255));
+            ui.label(format!("Queue depth: {}", last(&depths)));
+            sparkline(ui, &depths, WARNING_ORANGE);
+            if let Err(e) = latest {
+                ui.colored_label(Color32::RED, format!("Last poll failed: {e}"));
+            }
+        });
+}
+
+/// Small line plot of `values`, scaled to their own maximum. Missing samples break the line.
+fn sparkline(ui: &mut Ui, values: &[Option<f64>], color: Color32) {
+    let size = Vec2::new(ui.available_width().min(320.0), 40.0);
+    let (rect, _) = ui.allocate_exact_size(size, egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_stroke(rect, 2.0, ui.visuals().widgets.noninteractive.bg_stroke);
+
+    let max = values.iter().flatten().copied().fold(1.0, f64::max);
+    let step = rect.width() / (values.len().max(2) - 1) as f32;
+    let mut segment = Vec::new();
+    for (index, value) in values.iter().enumerate() {
+        if let Some(value) = value {
+            segment.push(egui::pos2(
+                rect.left() + index as f32 * step,
+                rect.bottom() - (*value / max) as f32 * rect.height(),
+            ));
+        } else if !segment.is_empty() {
+            painter.add(egui::Shape::line(
+                std::mem::take(&mut segment),
+                egui::Stroke::new(1.5, color),
+            ));
+        }
+    }
+    if !segment.is_empty() {
+        painter.add(egui::Shape::line(segment, egui::Stroke::new(1.5, color)));
+    }
+}
+
+/// "Test Login" and the result of the last run.
+fn login_test_row(app: &mut ThetaApp, ui: &mut Ui) {
+    let testing = app.login_test.is_some();
+    if ui
+        .add_enabled(!testing, egui::Button::new("Test Login"))
+        .on_hover_text(
+            "Start the terminal briefly with these credentials and stop it once it reports \
+             whether the login worked",
+        )
+        .clicked()
+    {
+        app.test_login();
+    }
+    if testing {
+        ui.spinner();
+    } else {
+        match &app.login_test_result {
+            Some(Ok(_)) => {
+                ui.colored_label(Color32::from_rgb(0, 180, 0), "Login OK");
+            }
+            Some(Err(e)) => {
+                ui.colored_label(Color32::RED, e);
+            }
+            None => {}
+        }
+    }
+}
+
+//
+// ────────────────────────────────────────────────────────────────────────────
+//   :: Tab 2: Terminal
+// ────────────────────────────────────────────────────────────────────────────
+//
+pub fn show_terminal_tab(app: &mut ThetaApp, ui: &mut Ui) {
+    ui.horizontal(|ui| {
+        if ui.button("Copy Output").clicked() {
+            let text = ansi::strip(&app.log_buffer.text()).into_owned();
+            ui.output_mut(|o| o.copied_text = text);
+        }
+        if ui.button("Save Log…").clicked() {
+            let name = format!(
+                "thetadata_log_{}.txt",
+                chrono::Local::now().format("%Y%m%d_%H%M%S")
+            );
+            if let Some(file) = FileDialog::new()
+                .add_filter("Text", &["txt", "log"])
+                .set_file_name(&name)
+                .save_file()
+            {
+                app.save_log(&file);
+            }
+        }
+        if ui.button("Clear").clicked() {
+            app.clear_log();
+        }
+        if ui
+            .checkbox(&mut app.follow_output, "Follow output")
+            .on_hover_text("Keep the view at the newest line; untick to read older output")
+            .changed()
+            && app.follow_output
+        {
+            // Sticking only holds the bottom once there; get back there first.
+            app.scroll_log_to_bottom = true;
+        }
+        ui.add_enabled(
+            !app.lightweight_log_view,
+            egui::Checkbox::new(&mut app.wrap_log_lines, "Wrap lines"),
+        )
+        .on_disabled_hover_text("The lightweight view keeps each line on one row");
+        ui.checkbox(&mut app.lightweight_log_view, "Lightweight view")
+            .on_hover_text(
+                "Lay out only the lines on screen instead of the whole log. \
+                 Lines don't wrap and can't be drag-selected; Alt+click still copies a line.",
+            );
+        ui.checkbox(&mut app.show_timestamps, "Timestamps")
+            .on_hover_text("Prefix new lines with the time they were received");
+        ui.checkbox(&mut app.order_output_by_time, "Order by time")
+            .on_hover_text(
+                "Merge stdout and stderr in the order lines were read, so stack traces \
+                 aren't scrambled. Delays output by about 100 ms.",
+            );
+        if let Some(frame_time) = app.average_frame_time() {
+            ui.weak(format!("{:.1} ms/frame", frame_time.as_secs_f64() * 1000.0))
+                .on_hover_text(
+                    "Average time to build a frame over the last 60, for comparing the two \
+                     views with a large log",
+                );
+        }
+    });
+    let lightweight = app.lightweight_log_view;
+    // The lightweight view reads lines straight from the buffer; don't join them every frame.
+    let log_text = if lightweight {
+        String::new()
+    } else {
+        app.log_buffer.text()
+    };
+    let (filtered, matched, matching_rows) = if lightweight {
+        let rows = filter_log_rows(&app.log_buffer, &app.log_filter);
+        let matched = rows.as_ref().map(Vec::len);
+        (String::new(), matched, rows)
+    } else {
+        let (filtered, matched) = filter_log_lines(&log_text, &app.log_filter);
+        (filtered, matched, None)
+    };
+    // Escapes are kept in the log and only interpreted here, for display.
+    let (shown_text, color_spans) = ansi::parse(&filtered);
+    ui.horizontal(|ui| {
+        ui.label("🔍");
+        ui.add(
+            TextEdit::singleline(&mut app.log_filter)
+                .hint_text("Filter lines…")
+                .desired_width(220.0),
+        );
+        if let Some(matched) = matched {
+            let total = app.log_buffer.line_count();
+            ui.weak(format!("{matched} of {total} lines match"));
+            if ui
+                .small_button("✖")
+                .on_hover_text("Clear the filter")
+                .clicked()
+            {
+                app.log_filter.clear();
+            }
+        }
+    });
+    if let Some(tail) = &app.output_tail {
+        ui.colored_label(
+            Color32::YELLOW,
+            format!(
+                "Output is redirected to {} (refreshed every second).",
+                tail.path.display()
+            ),
+        );
+    } else if app.process.is_some() && app.capture_output_to_file {
+        ui.weak("Output-to-file mode takes effect the next time the terminal starts.");
+    }
+    if let Some(every) = app.flood_guard.sampling() {
+        let kept = if app.file_log.is_some() {
+            "Every line is still written to the log file."
+        } else {
+            "Turn on the log file to keep every line."
+        };
+        ui.colored_label(
+            WARNING_ORANGE,
+            format!(
+                "⚠ Sampling active: ~{} lines/s, showing 1 in {every}. {kept}",
+                app.flood_guard.last_rate
+            ),
+        );
+    }
+
+    // Macros and stdin input
+    let running = app.stdin.is_some();
+    if !app.macros.is_empty() {
+        ui.horizontal_wrapped(|ui| {
+            for index in 0..app.macros.len() {
+                let name = app.macros[index].name.clone();
+                if ui
+                    .add_enabled(running, egui::Button::new(name).small())
+                    .on_hover_text(app.macros[index].commands.clone())
+                    .clicked()
+                {
+                    app.run_macro(index);
+                }
+            }
+        });
+    }
+    ui.horizontal(|ui| {
+        let input = ui.add_enabled(
+            running,
+            TextEdit::singleline(&mut app.stdin_input)
+                .hint_text("Send a command to the terminal…")
+                .desired_width(ui.available_width() - 60.0),
+        );
+        let submitted = input.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        let clicked = ui.add_enabled(running, egui::Button::new("Send")).clicked();
+        if (submitted || clicked) && !app.stdin_input.is_empty() {
+            let line = std::mem::take(&mut app.stdin_input);
+            app.send_stdin_line(&line);
+            input.request_focus();
+        }
+    });
+    recent_errors_panel(app, ui);
+    ui.add_space(4.0);
+
+    if lightweight {
+        show_log_rows(app, ui, matching_rows.as_deref());
+        return;
+    }
+
+    let wrap = app.wrap_log_lines;
+    let mut layouter_fn = |ui: &egui::Ui, text: &str, wrap_width: f32| {
+        // The spans index into the display text; anything else is drawn without them.
+        let spans: &[ansi::Span] = if text == shown_text {
+            &color_spans
+        } else {
+            &[]
+        };
+        layout_log_text(
+            ui,
+            text,
+            if wrap { wrap_width } else { f32::INFINITY },
+            spans,
+        )
+    };
+
+    // Make the terminal output fill all remaining height
+    let available = ui.available_size();
+    let scroll_area = if wrap {
+        ScrollArea::vertical()
+    } else {
+        ScrollArea::both()
+    };
+    // Auto-scroll region
+    scroll_area
+        .stick_to_bottom(app.follow_output)
+        .show(ui, |ui| {
+            let mut display_buffer = shown_text.clone();
+            let mut output = TextEdit::multiline(&mut display_buffer)
+                .font(egui::TextStyle::Monospace)
+                .lock_focus(true)
+                .desired_rows(10)
+                .desired_width(f32::INFINITY)
+                .min_size(available)
+                .margin(Vec2::new(0.0, 4.0))
+                .interactive(true)
+                .layouter(&mut layouter_fn)
+                .show(ui);
+
+            if std::mem::take(&mut app.scroll_log_to_bottom) {
+                ui.scroll_to_cursor(Some(egui::Align::BOTTOM));
+            }
+
+            // Alt+click copies the clicked line; plain clicks and drags select as usual.
+            if output.response.clicked() && ui.input(|i| i.modifiers.alt) {
+                if let Some(pos) = output.response.interact_pointer_pos() {
+                    let cursor = output.galley.cursor_from_pos(pos - output.text_draw_pos);
+                    if let Some(line) = line_at_char(&shown_text, cursor.ccursor.index) {
+                        let line = line.to_string();
+                        ui.output_mut(|o| o.copied_text = line);
+                        app.show_toast("Copied line");
+                    }
+                }
+            }
+
+            // Scroll to and select a line picked in the Recent errors panel.
+            // Offsets refer to the unfiltered log; the panel clears the filter first.
+            if !app.log_filter.is_empty() {
+                return;
+            }
+            if let Some(offset) = app.log_jump_target.take() {
+                // Lines trimmed off the front of the buffer can't be shown any more.
+                let Some(offset) = app.log_buffer.local_offset(offset) else {
+                    app.show_toast("That line is no longer in the on-screen log");
+                    return;
+                };
+                let Some(before) = log_text.get(..offset) else {
+                    return;
+                };
+                let line = ansi::strip(log_text[offset..].lines().next().unwrap_or(""));
+                let start = CCursor::new(ansi::strip(before).chars().count());
+                let end = CCursor::new(start.index + line.chars().count());
+                let cursor = output.galley.from_ccursor(start);
+                let rect = output
+                    .galley
+                    .pos_from_cursor(&cursor)
+                    .translate(output.text_draw_pos.to_vec2());
+                ui.scroll_to_rect(rect, Some(egui::Align::Center));
+                output
+                    .state
+                    .set_ccursor_range(Some(CCursorRange::two(start, end)));
+                output.state.store(ui.ctx(), output.response.id);
+            }
+        });
+}
+
+/// Read-only look at a browsed file before it replaces the editor contents.
+fn config_preview_window(app: &mut ThetaApp, ui: &mut Ui, path: &str, text: &str) {
+    egui::Window::new("Load config?")
+        .collapsible(false)
+        .resizable(true)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ui.ctx(), |ui| {
+            ui.label(path);
+            ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                let mut view: &str = text;
+                syntax_highlight_editor(ui, &mut view, app.theme, &[], &[], None, false);
+            });
+            let dirty = app.config_is_dirty();
+            if dirty {
+                ui.colored_label(WARNING_ORANGE, "Loading discards your unsaved edits.");
+            }
+            ui.horizontal(|ui| {
+                let load = if dirty {
+                    "Discard edits and load"
+                } else {
+                    "Load"
+                };
+                if ui.button(load).clicked() {
+                    app.config_preview = None;
+                    match app.load_config_file(path) {
+                        Ok(()) => app.append_log("Config file loaded from browse.\n"),
+                        Err(e) => {
+                            app.add_notification(
+                                Level::Error,
+                                &format!("Failed to load config file: {e}"),
+                            );
+                        }
+                    }
+                }
+                if ui.button("Cancel").clicked() {
+                    app.config_preview = None;
+                }
+            });
+        });
+}
+
+/// Asks what to do with unsaved edits before loading `path` over them.
+fn discard_edits_window(app: &mut ThetaApp, ui: &mut Ui, path: &str) {
+    egui::Window::new("Unsaved changes")
+        .collapsible(false)
+        .resizable(false)
+        .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+        .show(ui.ctx(), |ui| {
+            ui.label(format!(
+                "The editor has unsaved changes. Loading {path} replaces them."
+            ));
+            ui.horizontal(|ui| {
+                if ui.button("Save, then load").clicked() {
+                    app.resolve_pending_config_load(true);
+                }
+                if ui.button("Discard edits and load").clicked() {
+                    app.resolve_pending_config_load(false);
+                }
+                if ui.button("Cancel").clicked() {
+                    app.pending_config_load = None;
+                }
+            });
+        });
+}
+
+/// The file's detected encoding and the opt-in conversion to UTF-8, with a warning when
+/// converting changes how some characters are stored.
+fn encoding_row(app: &mut ThetaApp, ui: &mut Ui) {
+    let is_1252 = app.config_encoding == ConfigEncoding::Windows1252;
+    ui.horizontal(|ui| {
+        ui.label("Encoding:");
+        ui.strong(if is_1252 { "Windows-1252" } else { "UTF-8" });
+        ui.add_enabled(
+            is_1252 && app.config_view_mode != ConfigViewMode::ViewOnly,
+            egui::Checkbox::new(&mut app.save_config_as_utf8, "Save as UTF-8"),
+        )
+        .on_hover_text("Convert the file to UTF-8 on the next save")
+        .on_disabled_hover_text("The file is already UTF-8");
+    });
+    if is_1252 && app.save_config_as_utf8 {
+        let changed: Vec<char> = app
+            .thetadata_config_text
+            .chars()
+            .filter(|c| !c.is_ascii())
+            .collect();
+        if !changed.is_empty() {
+            let sample: String = changed.iter().take(5).collect();
+            ui.colored_label(
+                WARNING_ORANGE,
+                format!(
+                    "⚠ {} non-ASCII character(s) such as \"{sample}\" will be stored differently; \
+                     anything still reading the file as Windows-1252 will see them garbled.",
+                    changed.len()
+                ),
+            );
+        }
+    }
+}
+
+/// Duplicate key count and policy, with the cleanup button for the keep-first/last
+/// policies. Returns the lines to highlight: every duplicate when warning, otherwise the
+/// ones the cleanup would remove.
+fn duplicate_keys_row(app: &mut ThetaApp, ui: &mut Ui) -> Vec<usize> {
+    let duplicates = duplicate_keys(&parse_entries(&app.thetadata_config_text));
+    let editable = app.config_view_mode != ConfigViewMode::ViewOnly;
+    ui.horizontal_wrapped(|ui| {
+        ui.label("Duplicate keys:");
+        if duplicates.is_empty() {
+            ui.label("none");
+        } else {
+            let keys: Vec<&str> = duplicates.iter().map(|(key, _)| key.as_str()).collect();
+            ui.colored_label(
+                WARNING_ORANGE,
+                format!("⚠ {} ({})", duplicates.len(), keys.join(", ")),
+            );
+        }
+        egui::ComboBox::from_id_source("duplicate_key_policy")
+            .selected_text(app.duplicate_key_policy.label())
+            .show_ui(ui, |ui| {
+                for policy in DuplicateKeyPolicy::ALL {
+                    ui.selectable_value(&mut app.duplicate_key_policy, policy, policy.label());
+                }
+            })
+            .response
+            .on_hover_text("ThetaTerminal doesn't document which occurrence of a key wins");
+        if app.duplicate_key_policy != DuplicateKeyPolicy::Warn
+            && ui
+                .add_enabled(
+                    editable && !duplicates.is_empty(),
+                    egui::Button::new("Remove duplicates"),
+                )
+                .on_hover_text("Delete the losing lines; comments are kept")
+                .clicked()
+        {
+            app.remove_duplicate_keys();
+        }
+    });
+    match app.duplicate_key_policy {
+        DuplicateKeyPolicy::Warn => {
+            let mut lines: Vec<usize> = duplicates
+                .into_iter()
+                .flat_map(|(_, lines)| lines)
+                .collect();
+            lines.sort_unstable();
+            lines
+        }
+        DuplicateKeyPolicy::KeepFirst => losing_duplicate_lines(&duplicates, false),
+        DuplicateKeyPolicy::KeepLast => losing_duplicate_lines(&duplicates, true),
+    }
+}
+
+/// Input for a file path sized to leave room for a Browse button. With `short` set, only the
+/// file name is shown, with the full path on hover; the stored path stays complete.
+fn path_field(ui: &mut Ui, path: &mut String, short: bool) {
+    let name = Path::new(path.as_str())
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string());
+    match name {
+        Some(name) if short => {
+            ui.add_sized(
+                [ui.available_width() - 60.0, ui.spacing().interact_size.y],
+                egui::Label::new(name).truncate(true),
+            )
+            .on_hover_text(path.as_str());
+        }
+        _ => {
+            ui.add(TextEdit::singleline(path).desired_width(ui.available_width() - 60.0));
+        }
+    }
+}
+
+/// The lines of `text` containing `filter` (case-insensitively) and how many there are, or
+/// the text unchanged and `None` when the filter is empty.
+fn filter_log_lines(text: &str, filter: &str) -> (String, Option<usize>) {
+    if filter.is_empty() {
+        return (text.to_string(), None);
+    }
+    let needle = filter.to_lowercase();
+    let mut shown = String::new();
+    let mut matched = 0;
+    for line in text.split_inclusive('\n') {
+        if ansi::strip(line).to_lowercase().contains(&needle) {
+            shown.push_str(line);
+            matched += 1;
+        }
+    }
+    (shown, Some(matched))
+}
+
+/// Indices of the buffered lines matching `filter`, like `filter_log_lines`; `None` when
+/// there is no filter.
+fn filter_log_rows(buffer: &LogBuffer, filter: &str) -> Option<Vec<usize>> {
+    if filter.is_empty() {
+        return None;
+    }
+    let needle = filter.to_lowercase();
+    let rows = (0..buffer.line_count())
+        .filter(|&index| {
+            buffer
+                .line(index)
+                .is_some_and(|line| ansi::strip(line).to_lowercase().contains(&needle))
+        })
+        .collect();
+    Some(rows)
+}
+
+/// The log laid out one visible row at a time, so the work per frame stays the same however
+/// long the buffer gets. `rows` are the lines passing the filter, or `None` for all of them.
+fn show_log_rows(app: &mut ThetaApp, ui: &mut Ui, rows: Option<&[usize]>) {
+    let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+    let row_pitch = row_height + ui.spacing().item_spacing.y;
+    let total_rows = rows.map_or(app.log_buffer.line_count(), <[usize]>::len);
+    let mut scroll_area = ScrollArea::both()
+        .auto_shrink([false, false])
+        .stick_to_bottom(app.follow_output);
+    if std::mem::take(&mut app.scroll_log_to_bottom) {
+        scroll_area = scroll_area.vertical_scroll_offset(total_rows as f32 * row_pitch);
+    }
+    // Offsets refer to the unfiltered log; the Recent errors panel clears the filter first.
+    if rows.is_none() {
+        if let Some(offset) = app.log_jump_target.take() {
+            match app.log_buffer.line_index(offset) {
+                Some(index) => {
+                    let centered = index as f32 * row_pitch - ui.available_height() / 2.0;
+                    scroll_area = scroll_area.vertical_scroll_offset(centered.max(0.0));
+                }
+                None => app.show_toast("That line is no longer in the on-screen log"),
+            }
+        }
+    }
+
+    let mut copied = false;
+    scroll_area.show_rows(ui, row_height, total_rows, |ui, visible| {
+        for row in visible {
+            let index = rows.map_or(row, |rows| rows[row]);
+            let Some(line) = app.log_buffer.line(index) else {
+                continue;
+            };
+            let (text, spans) = ansi::parse(line);
+            let galley = layout_log_text(ui, &text, f32::INFINITY, &spans);
+            let response = ui.add(egui::Label::new(galley).sense(egui::Sense::click()));
+            // Alt+click copies the clicked line, as in the full view.
+            if response.clicked() && ui.input(|i| i.modifiers.alt) && !text.is_empty() {
+                ui.output_mut(|o| o.copied_text = text);
+                copied = true;
+            }
+        }
+    });
+    if copied {
+        app.show_toast("Copied line");
+    }
+}
+
+/// The full line of `text` containing the character at `char_index`, without its newline.
+fn line_at_char(text: &str, char_index: usize) -> Option<&str> {
+    let byte = text
+        .char_indices()
+        .nth(char_index)
+        .map_or(text.len(), |(i, _)| i);
+    let start = text[..byte].rfind('\n').map_or(0, |i| i + 1);
+    let end = text[byte..].find('\n').map_or(text.len(), |i| byte + i);
+    let line = text[start..end].trim_end_matches('\r');
+    (!line.is_empty()).then_some(line)
+}
+
+/// Collapsible list of the latest error and warning lines; clicking one jumps to it in the log.
+fn recent_errors_panel(app: &mut ThetaApp, ui: &mut Ui) {
+    if app.recent_errors.is_empty() {
+        return;
+    }
+    egui::CollapsingHeader::new(format!("Recent errors ({})", app.recent_errors.len()))
+        .id_source("recent_errors")
+        .default_open(false)
+        .show(ui, |ui| {
+            let mut jump_to = None;
+            for error in app.recent_errors.iter().rev() {
+                let color = match error.severity {
+                    Severity::Error => Color32::RED,
+                    _ => WARNING_ORANGE,
+                };
+                ui.horizontal(|ui| {
+                    ui.weak(app.format_timestamp(error.received_at));
+                    let label =
+                        egui::Label::new(egui::RichText::new(&error.text).monospace().color(color))
+                            .truncate(true)
+                            .sense(egui::Sense::click());
+                    if ui.add(label).on_hover_text("Show in log").clicked() {
+                        jump_to = Some(error.log_offset);
+                    }
+                });
+            }
+            if ui.small_button("Clear").clicked() {
+                app.recent_errors.clear();
+            }
+            if let Some(offset) = jump_to {
+                // Following the output would pull the view straight back to the bottom.
+                app.follow_output = false;
+                app.log_filter.clear();
+                app.log_jump_target = Some(offset);
+            }
+        });
+}
+
+/// Lays out the terminal log in the monospace font.
+/// Passing `f32::INFINITY` as the wrap width keeps each log line on a single row.
+/// Error lines are drawn red, warnings orange and user-action markers in the link color.
+/// ANSI-colored `spans` (byte ranges of `text`, in order) take precedence within a line.
+fn layout_log_text(
+    ui: &egui::Ui,
+    text: &str,
+    wrap_width: f32,
+    spans: &[ansi::Span],
+) -> Arc<Galley> {
+    let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+    let text_color = ui.visuals().text_color();
+    let strong_color = ui.visuals().strong_text_color();
+    let marker_color = ui.visuals().hyperlink_color;
+    let mut job = LayoutJob::default();
+    job.wrap.max_width = wrap_width;
+    let mut spans = spans.iter().peekable();
+    let mut offset = 0;
+    for chunk in text.split_inclusive('\n') {
+        let line_color = if chunk.starts_with(ACTION_MARKER_PREFIX) {
+            marker_color
+        } else {
+            match severity::classify(chunk) {
+                Severity::Error => Color32::RED,
+                Severity::Warning => WARNING_ORANGE,
+                Severity::Info => text_color,
+            }
+        };
+        let end = offset + chunk.len();
+        while offset < end {
+            while spans.next_if(|span| span.range.end <= offset).is_some() {}
+            let (next, color) = match spans.peek() {
+                Some(span) if span.range.start <= offset => {
+                    let color =
+                        span.color
+                            .unwrap_or(if span.bold { strong_color } else { line_color });
+                    (span.range.end.min(end), color)
+                }
+                Some(span) => (span.range.start.min(end), line_color),
+                None => (end, line_color),
+            };
+            job.append(
+                &text[offset..next],
+                0.0,
+                TextFormat::simple(font_id.clone(), color),
+            );
+            offset = next;
+        }
+    }
+    ui.fonts(|fonts| fonts.layout_job(job))
+}
+
+//
+// ────────────────────────────────────────────────────────────────────────────
+//   :: Tab 3: Config (Syntax-Highlighted Editor with Refresh)
+// ────────────────────────────────────────────────────────────────────────────
+//
+pub fn show_config_tab(app: &mut ThetaApp, ui: &mut Ui) {
+    if let Some(detected) = app.running_config_mismatch() {
+        ui.group(|ui| {
+            ui.colored_label(
+                WARNING_ORANGE,
+                "⚠ You're editing a different file than the running terminal is using.",
+            );
+            ui.label(format!("Terminal loaded: {detected}"));
+            let switch = ui.add_enabled(
+                !app.config_is_dirty(),
+                egui::Button::new("Switch to the terminal's config"),
+            );
+            if switch
+                .on_disabled_hover_text("Save your edits first")
+                .clicked()
+            {
+                match app.load_config_file(&detected) {
+                    Ok(()) => app.append_log("Switched to the config the terminal is using.\n"),
+                    Err(e) => {
+                        app.add_notification(
+                            Level::Error,
+                            &format!("Failed to load config file: {e}"),
+                        );
+                    }
+                }
+            }
+        });
+        ui.add_space(8.0);
+    }
+
+    save_summary_panel(app, ui);
+
+    egui::CollapsingHeader::new("ThetaData Config File")
+        .default_open(true)
+        .show(ui, |ui| {
+            // 1) The path input
+            ui.horizontal(|ui| {
+                ui.label("ThetaData Config File Path:");
+            });
+            ui.horizontal(|ui| {
+                path_field(ui, &mut app.thetadata_config_path, app.show_path_basenames);
+                if ui.button("Browse").clicked() {
+                    if let Some(file) = FileDialog::new().pick_file() {
+                        let path = file.to_string_lossy().to_string();
+                        match app.read_config_with_retry(&path) {
+                            Ok((text, _)) => app.config_preview = Some((path, text)),
+                            Err(e) => {
+                                app.add_notification(
+                                    Level::Error,
+                                    &format!("Failed to read config from browse: {e}"),
+                                );
+                            }
+                        }
+                    }
+                }
+            });
+
+            // 2) "Get from Terminal" button
+            ui.horizontal(|ui| {
+                if ui.button("Get from Terminal").clicked() {
+                    if let Some(detected) = app.last_detected_config_path.clone() {
+                        app.request_config_load(detected, "terminal detection");
+                    } else {
+                        app.add_notification(
+                            Level::Warning,
+                            "No config path detected yet. Launch the terminal first.",
+                        );
+                    }
+                }
+
+                // 3) NEW: Refresh button
+                if ui.button("Refresh").clicked() {
+                    if app.thetadata_config_path.is_empty() {
+                        app.add_notification(Level::Warning, "No config path set to refresh.");
+                    } else if app.check_config_file_exists() {
+                        let path = app.thetadata_config_path.clone();
+                        app.request_config_load(path, "disk (refresh)");
+                    }
+                }
+
+                if ui
+                    .button("Copy launch args")
+                    .on_hover_text(
+                        "Copy the working directory and config details the GUI launches the \
+                         terminal with, to reproduce the launch by hand",
+                    )
+                    .clicked()
+                {
+                    let args = app.config_launch_args();
+                    ui.output_mut(|o| o.copied_text = args);
+                    app.show_toast("Launch args copied");
+                }
+
+                ui.checkbox(&mut app.auto_load_detected_config, "Auto-load detected")
+                    .on_hover_text(
+                        "Load the config the terminal reports using, unless you have unsaved edits",
+                    );
+                ui.add_enabled(
+                    app.auto_load_detected_config,
+                    egui::DragValue::new(&mut app.auto_load_delay_ms)
+                        .clamp_range(0..=10_000)
+                        .speed(50)
+                        .prefix("after ")
+                        .suffix(" ms"),
+                )
+                .on_hover_text("Wait before loading, then load once the file size stops changing");
+            });
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut app.backup_config_on_start, "Back up before each start")
+                    .on_hover_text(
+                        "Copy the config to <name>.<timestamp>.bak next to it before the \
+                         terminal starts, since the terminal may rewrite it",
+                    );
+                ui.checkbox(&mut app.keep_backups, "Back up before each save")
+                    .on_hover_text(
+                        "Copy the config to <name>.<timestamp>.bak before Save overwrites it",
+                    );
+                ui.label("Keep");
+                ui.add(egui::DragValue::new(&mut app.backup_retention).clamp_range(1..=100));
+                ui.label("backups");
+            });
+
+            if app.config_changed_on_disk {
+                ui.group(|ui| {
+                    ui.colored_label(
+                        WARNING_ORANGE,
+                        "The config file was changed outside the app.",
+                    );
+                    if app.config_is_dirty() {
+                        ui.label("Reloading discards your unsaved edits.");
+                    }
+                    ui.horizontal(|ui| {
+                        if ui.button("Reload").clicked() {
+                            let path = app.thetadata_config_path.clone();
+                            match app.load_config_file(&path) {
+                                Ok(()) => app.append_log("Config file reloaded from disk.\n"),
+                                Err(e) => {
+                                    app.add_notification(
+                                        Level::Error,
+                                        &format!("Failed to reload config: {e}"),
+                                    );
+                                }
+                            }
+                        }
+                        if ui
+                            .button("Keep mine")
+                            .on_hover_text(
+                                "Keep the editor's version; saving will overwrite the file",
+                            )
+                            .clicked()
+                        {
+                            app.ignore_config_disk_change();
+                        }
+                    });
+                });
+            }
+
+            // 4) Save As… and recently used files
+            ui.horizontal(|ui| {
+                if ui
+                    .button("Open in external editor")
+                    .on_hover_text(
+                        "Edit the file in another program; saved changes are offered for reload",
+                    )
+                    .clicked()
+                {
+                    app.open_config_externally();
+                }
+                if ui
+                    .button("Save As…")
+                    .on_hover_text("Write the current text to a new file and switch to it")
+                    .clicked()
+                {
+                    pick_save_as_path(app);
+                }
+
+                let mut chosen = None;
+                egui::ComboBox::from_id_source("recent_configs")
+                    .selected_text("Recent…")
+                    .show_ui(ui, |ui| {
+                        for path in &app.recent_config_paths {
+                            if ui.selectable_label(false, path).clicked() {
+                                chosen = Some(path.clone());
+                            }
+                        }
+                    });
+                if let Some(path) = chosen {
+                    app.request_config_load(path, "recent files");
+                }
+            });
+
+            if let Some((path, text)) = app.config_preview.clone() {
+                config_preview_window(app, ui, &path, &text);
+            }
+
+            if let Some((path, _)) = app.pending_config_load.clone() {
+                discard_edits_window(app, ui, &path);
+            }
+
+            if let Some(path) = app.pending_save_as.clone() {
+                egui::Window::new("Overwrite file?")
+                    .collapsible(false)
+                    .resizable(false)
+                    .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                    .show(ui.ctx(), |ui| {
+                        ui.label(format!("{path} already exists. Replace it?"));
+                        ui.horizontal(|ui| {
+                            if ui.button("Overwrite").clicked() {
+                                app.pending_save_as = None;
+                                app.save_config_as(&path);
+                            }
+                            if ui.button("Cancel").clicked() {
+                                app.pending_save_as = None;
+                            }
+                        });
+                    });
+            }
+
+            // Ctrl/Cmd+M cycles the view modes; they all work on the same text, so
+            // unsaved edits carry over.
+            if ui.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::M)) {
+                app.config_view_mode = app.config_view_mode.next();
+            }
+
+            ui.add_space(8.0);
+            ui.horizontal(|ui| {
+                ui.label("View:");
+                for mode in ConfigViewMode::ALL {
+                    ui.selectable_value(&mut app.config_view_mode, mode, mode.label());
+                }
+                ui.weak("(Ctrl+M to cycle)");
+                if app.config_view_mode != ConfigViewMode::Structured {
+                    ui.checkbox(&mut app.config_line_numbers, "Line numbers");
+                }
+            });
+            if app.config_view_mode != ConfigViewMode::ViewOnly {
+                // Taken before the editor draws, so its own per-widget undo doesn't also run.
+                // Another focused field (Find, Replace) keeps its own undo.
+                let ours = ui
+                    .memory(|m| m.focus())
+                    .map_or(true, |id| id == config_editor_id());
+                let (redo, undo) = if ours {
+                    ui.input_mut(|i| {
+                        let shift_command = egui::Modifiers {
+                            shift: true,
+                            ..egui::Modifiers::COMMAND
+                        };
+                        (
+                            i.consume_key(shift_command, egui::Key::Z),
+                            i.consume_key(egui::Modifiers::COMMAND, egui::Key::Z),
+                        )
+                    })
+                } else {
+                    (false, false)
+                };
+                ui.horizontal(|ui| {
+                    let undo_clicked = ui
+                        .add_enabled(
+                            !app.config_undo_stack.is_empty(),
+                            egui::Button::new("⟲ Undo"),
+                        )
+                        .on_hover_text("Ctrl+Z")
+                        .clicked();
+                    let redo_clicked = ui
+                        .add_enabled(
+                            !app.config_redo_stack.is_empty(),
+                            egui::Button::new("⟳ Redo"),
+                        )
+                        .on_hover_text("Ctrl+Shift+Z")
+                        .clicked();
+                    if undo || undo_clicked {
+                        app.undo_config_edit();
+                    }
+                    if redo || redo_clicked {
+                        app.redo_config_edit();
+                    }
+                });
+            }
+            encoding_row(app, ui);
+            let flagged = duplicate_keys_row(app, ui);
+
+            match app.config_view_mode {
+                ConfigViewMode::ViewOnly => {
+                    ui.label("Viewing config (read-only):");
+                    let mut view: &str = &app.thetadata_config_text;
+                    syntax_highlight_editor(
+                        ui,
+                        &mut view,
+                        app.theme,
+                        &flagged,
+                        &[],
+                        None,
+                        app.config_line_numbers,
+                    );
+                }
+                ConfigViewMode::Structured => structured_config_editor(app, ui),
+                ConfigViewMode::Text => {
+                    let matches = find_replace_bar(app, ui);
+                    // Ctrl+/ isn't available as a key here, so Ctrl+D it is; Ctrl+K is
+                    // Stop everywhere (see handle_shortcuts).
+                    let toggle_key =
+                        ui.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::D));
+                    let toggle_clicked = ui
+                        .add_enabled(
+                            app.config_selection.is_some(),
+                            egui::Button::new("# Toggle comment"),
+                        )
+                        .on_hover_text("Comment or uncomment the selected lines (Ctrl+D)")
+                        .clicked();
+                    if toggle_key || toggle_clicked {
+                        app.toggle_comment_selection();
+                    }
+                    ui.label("Edit your config file below (with minimal syntax highlighting):");
+
+                    // Show the config file in a syntax-highlighted code editor
+                    let select = app.find_replace.select.take();
+                    // Kept once the editor loses focus, so the toggle button still has it.
+                    if let Some(selection) = syntax_highlight_editor(
+                        ui,
+                        &mut app.thetadata_config_text,
+                        app.theme,
+                        &flagged,
+                        &matches,
+                        select,
+                        app.config_line_numbers,
+                    ) {
+                        app.config_selection = Some(selection);
+                    }
+                }
+            }
+            unknown_keys_panel(app, ui);
+
+            if app.config_view_mode != ConfigViewMode::ViewOnly {
+                ui.add_space(16.0);
+                ui.label("Remember to click 'Save' at the bottom to persist changes.");
+                if ui
+                    .button("Apply & Restart")
+                    .on_hover_text("Save the config and restart the terminal if it is running")
+                    .clicked()
+                {
+                    app.mark_user_action("Apply & Restart");
+                    app.apply_and_restart();
+                }
+            }
+        });
+}
+
+/// Warnings for keys ThetaTerminal doesn't know, which it would silently ignore. Saving
+/// isn't blocked; the list just points at likely typos.
+fn unknown_keys_panel(app: &ThetaApp, ui: &mut Ui) {
+    let entries = parse_entries(&app.thetadata_config_text);
+    let unknown = known_keys::unknown_keys(&entries);
+    if unknown.is_empty() {
+        return;
+    }
+    ui.add_space(4.0);
+    ui.colored_label(
+        WARNING_ORANGE,
+        format!("⚠ {} unrecognized key(s):", unknown.len()),
+    );
+    for entry in unknown {
+        let hint = known_keys::suggestion(&entry.key)
+            .map(|known| format!(" — did you mean {known}?"))
+            .unwrap_or_default();
+        ui.label(format!("Line {}: {}{hint}", entry.line + 1, entry.key));
+    }
+}
+
+/// Find/replace fields over the config text with a live match count. Replaced text stays
+/// unsaved like any other edit, and each replace is its own step for the editor's Undo.
+/// Returns the byte ranges of the matches, for the editor to highlight.
+fn find_replace_bar(app: &mut ThetaApp, ui: &mut Ui) -> Vec<Range<usize>> {
+    let mut matches = Vec::new();
+    let mut replaced = None;
+    egui::CollapsingHeader::new("🔍 Find & replace")
+        .default_open(false)
+        .show(ui, |ui| {
+            let fr = &mut app.find_replace;
+            ui.horizontal(|ui| {
+                ui.label("Find:");
+                ui.add(TextEdit::singleline(&mut fr.find).desired_width(160.0));
+                ui.label("Replace:");
+                ui.add(TextEdit::singleline(&mut fr.replace).desired_width(160.0));
+            });
+            ui.horizontal_wrapped(|ui| {
+                ui.checkbox(&mut fr.case_insensitive, "Ignore case");
+                ui.checkbox(&mut fr.regex, "Regex")
+                    .on_hover_text("Use $1, $2, … in the replacement for capture groups");
+                ui.checkbox(&mut fr.code_only, "Code only")
+                    .on_hover_text("Leave # and ! comment lines alone");
+            });
+
+            let matcher = match fr.matcher() {
+                None => None,
+                Some(Ok(re)) => Some(re),
+                Some(Err(e)) => {
+                    ui.colored_label(Color32::RED, format!("Invalid regex: {e}"));
+                    None
+                }
+            };
+            matches = matcher
+                .as_ref()
+                .map_or_else(Vec::new, |re| fr.ranges(&app.thetadata_config_text, re));
+            let count = matches.len();
+            // The selected match, kept in range as edits add and remove matches.
+            let current = fr.current.filter(|_| count > 0).map(|i| i % count);
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(count > 0, egui::Button::new("Find next"))
+                    .clicked()
+                {
+                    let next = current.map_or(0, |i| (i + 1) % count);
+                    fr.current = Some(next);
+                    fr.select = Some(matches[next].clone());
+                }
+                if ui
+                    .add_enabled(count > 0, egui::Button::new("Replace"))
+                    .on_hover_text("Replace the selected match, or the first, and select the next")
+                    .clicked()
+                {
+                    if let Some(re) = &matcher {
+                        let index = current.unwrap_or(0);
+                        if let Some(text) = fr.replace_at(&app.thetadata_config_text, re, index) {
+                            app.thetadata_config_text = text;
+                            replaced = Some(1);
+                            // What was the next match now has this index.
+                            let remaining = fr.ranges(&app.thetadata_config_text, re);
+                            fr.current = (!remaining.is_empty()).then(|| index % remaining.len());
+                            fr.select = fr.current.map(|i| remaining[i].clone());
+                            matches = remaining;
+                        }
+                    }
+                }
+                if ui
+                    .add_enabled(count > 0, egui::Button::new("Replace All"))
+                    .clicked()
+                {
+                    if let Some(re) = &matcher {
+                        let (text, n) = fr.replace_all(&app.thetadata_config_text, re);
+                        app.thetadata_config_text = text;
+                        fr.current = None;
+                        replaced = Some(n);
+                        matches = fr.ranges(&app.thetadata_config_text, re);
+                    }
+                }
+                if let Some(i) = current {
+                    ui.label(format!("{} of {count} match(es)", i + 1));
+                } else if matcher.is_some() {
+                    ui.label(format!("{count} match(es)"));
+                }
+            });
+        });
+    if let Some(n) = replaced {
+        let find = app.find_replace.find.clone();
+        app.append_log(&format!(
+            "Replaced {n} occurrence(s) of \"{find}\" in the config.\n"
+        ));
+    }
+    matches
+}
+
+/// Receipt for the last save: where it went, how big it is, the newest backup and any
+/// warnings the file was saved with. Hides itself after a while or on ✖.
+fn save_summary_panel(app: &mut ThetaApp, ui: &mut Ui) {
+    let mut dismissed = false;
+    if let Some(summary) = app.visible_save_summary() {
+        ui.group(|ui| {
+            ui.horizontal_wrapped(|ui| {
+                ui.colored_label(Color32::from_rgb(0, 180, 0), "✔");
+                ui.label(format!(
+                    "Saved {} — {} lines, {} bytes",
+                    summary.path, summary.lines, summary.bytes
+                ));
+                if ui.small_button("✖").on_hover_text("Dismiss").clicked() {
+                    dismissed = true;
+                }
+            });
+            match &summary.latest_backup {
+                Some(backup) => ui.weak(format!("Latest backup: {}", backup.display())),
+                None => ui.weak("No backup of this file yet"),
+            };
+            for warning in &summary.warnings {
+                ui.colored_label(WARNING_ORANGE, format!("⚠ {warning}"));
+            }
+        });
+        ui.add_space(8.0);
+    }
+    if dismissed {
+        app.save_summary = None;
+    }
+}
+
+/// Ask for a destination for "Save As…", confirming before replacing an existing file.
+fn pick_save_as_path(app: &mut ThetaApp) {
+    let current = Path::new(&app.thetadata_config_path);
+    let suggested = match (current.file_stem(), current.extension()) {
+        (Some(stem), Some(ext)) => {
+            format!("{}_copy.{}", stem.to_string_lossy(), ext.to_string_lossy())
+        }
+        _ => "config_copy.properties".to_string(),
+    };
+    let mut dialog = FileDialog::new().set_file_name(&suggested);
+    if let Some(dir) = current.parent().filter(|d| d.is_dir()) {
+        dialog = dialog.set_directory(dir);
+    }
+    if let Some(file) = dialog.save_file() {
+        let path = file.to_string_lossy().to_string();
+        if file.exists() {
+            app.pending_save_as = Some(path);
+        } else {
+            app.save_config_as(&path);
+        }
+    }
+}
+
+/// Key/value grid over the config entries. Edits are staged and only written into
+/// `thetadata_config_text` on "Apply"; multi-key applies are confirmed with a summary first.
+fn structured_config_editor(app: &mut ThetaApp, ui: &mut Ui) {
+    let entries = parse_entries(&app.thetadata_config_text);
+    if entries.is_empty() {
+        ui.label("No key=value entries found in this config.");
+        return;
+    }
+
+    let regions = region_names(&entries);
+    if !regions.is_empty() {
+        ui.horizontal(|ui| {
+            ui.label("Region preset:");
+            egui::ComboBox::from_id_source("region_preset")
+                .selected_text("Choose…")
+                .show_ui(ui, |ui| {
+                    for region in &regions {
+                        if ui.selectable_label(false, region).clicked() {
+                            app.stage_region_preset(&entries, region);
+                        }
+                    }
+                });
+        });
+    }
+
+    // Region-scoped keys (hosts, credentials, endpoints) get a section per region so
+    // several regions can be managed side by side.
+    let (general, sections) = group_by_region(&entries, &regions);
+    structured_entry_grid(app, ui, "structured_config", &general);
+    for (region, region_entries) in &sections {
+        if region_entries.is_empty() {
+            continue;
+        }
+        egui::CollapsingHeader::new(format!("Region {region} ({})", region_entries.len()))
+            .id_source(("structured_region", region))
+            .default_open(true)
+            .show(ui, |ui| {
+                structured_entry_grid(app, ui, ("structured_region_grid", region), region_entries);
+            });
+    }
+
+    ui.horizontal(|ui| {
+        let staged = app.structured_edits.len();
+        if ui
+            .add_enabled(
+                staged > 0,
+                egui::Button::new(format!("Apply changes ({staged})")),
+            )
+            .clicked()
+        {
+            app.request_structured_apply();
+        }
+        if ui
+            .add_enabled(staged > 0, egui::Button::new("Discard"))
+            .clicked()
+        {
+            app.structured_edits.clear();
+        }
+    });
+
+    if let Some(changes) = app.pending_structured_apply.clone() {
+        egui::Window::new("Confirm config changes")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ui.ctx(), |ui| {
+                ui.label(format!("Changing: {}", describe_changes(&changes)));
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Apply").clicked() {
+                        app.apply_structured_edits(&changes);
+                    }
+                    if ui.button("Cancel").clicked() {
+                        app.pending_structured_apply = None;
+                    }
+                });
+            });
+    }
+}
+
+/// Editable key/value rows; edits are staged rather than written to the text directly.
+fn structured_entry_grid(
+    app: &mut ThetaApp,
+    ui: &mut Ui,
+    id_source: impl std::hash::Hash,
+    entries: &[ConfigEntry],
+) {
+    egui::Grid::new(id_source)
+        .num_columns(3)
+        .striped(true)
+        .show(ui, |ui| {
+            for entry in entries {
+                ui.label(&entry.key);
+                let mut value = app
+                    .structured_edits
+                    .get(&entry.line)
+                    .map(|c| c.new_value.clone())
+                    .unwrap_or_else(|| entry.value.clone());
+                if ui
+                    .add(TextEdit::singleline(&mut value).desired_width(200.0))
+                    .changed()
+                {
+                    app.stage_structured_edit(entry, value.clone());
+                }
+                match schema_default(&entry.key) {
+                    Some(default) => {
+                        if ui
+                            .add_enabled(value != default, egui::Button::new("↺").small())
+                            .on_hover_text(format!("Reset to default ({default})"))
+                            .clicked()
+                        {
+                            app.stage_structured_edit(entry, default.to_string());
+                        }
+                    }
+                    None => {
+                        ui.label("");
+                    }
+                }
+                ui.end_row();
+            }
+        });
+}
+
+/// Fixed, so the Config tab can tell whether its editor has keyboard focus before drawing it.
+fn config_editor_id() -> egui::Id {
+    egui::Id::new("config_editor")
+}
+
+/// A code editor that highlights lines starting with '#' as comments, and everything else in green.
+/// Using `split_inclusive('\n')` so edits occur at the correct position.
+/// Passing a `&str` buffer renders the same view read-only.
+/// `flagged` lines (sorted) get a warning background, e.g. duplicate keys.
+fn syntax_highlight_editor(
+    ui: &mut Ui,
+    text: &mut dyn TextBuffer,
+    theme: Theme,
+    flagged: &[usize],
+    matches: &[Range<usize>],
+    select: Option<Range<usize>>,
+    line_numbers: bool,
+) -> Option<Range<usize>> {
+    let mut layouter_fn = move |ui: &egui::Ui, code: &str, _wrap_width: f32| {
+        highlight_config_text(ui, code, theme, flagged, matches)
+    };
+
+    // Room for the widest line number, left of the editor.
+    let gutter_width = if line_numbers {
+        let digits = text.as_str().split('\n').count().to_string().len();
+        let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+        let digit_width = ui.fonts(|f| f.glyph_width(&font_id, '0'));
+        digits as f32 * digit_width + 8.0
+    } else {
+        0.0
+    };
+    let mut output = ui
+        .horizontal_top(|ui| {
+            ui.add_space(gutter_width);
+            TextEdit::multiline(text)
+                .id(config_editor_id())
+                .font(egui::TextStyle::Monospace)
+                .desired_rows(15)
+                .desired_width(ui.available_width())
+                .lock_focus(false)
+                .layouter(&mut layouter_fn)
+                .show(ui)
+        })
+        .inner;
+    if line_numbers {
+        paint_line_numbers(
+            ui,
+            &output.galley,
+            output.text_draw_pos,
+            &output.response.rect,
+        );
+    }
+
+    // The selection as it was typed, in bytes, for actions like toggling comments.
+    let code = output.galley.text();
+    let byte_offset = |ccursor: CCursor| {
+        code.char_indices()
+            .nth(ccursor.index)
+            .map_or(code.len(), |(offset, _)| offset)
+    };
+    let selection = output.cursor_range.map(|cursor| {
+        let range = cursor.as_ccursor_range();
+        let (a, b) = (byte_offset(range.primary), byte_offset(range.secondary));
+        a.min(b)..a.max(b)
+    });
+
+    // Select a match picked with "Find next" or "Replace" and bring it into view.
+    let Some(range) = select else {
+        return selection;
+    };
+    if range.end > code.len()
+        || !code.is_char_boundary(range.start)
+        || !code.is_char_boundary(range.end)
+    {
+        return selection;
+    }
+    let start = CCursor::new(code[..range.start].chars().count());
+    let end = CCursor::new(start.index + code[range.clone()].chars().count());
+    let cursor = output.galley.from_ccursor(start);
+    let rect = output
+        .galley
+        .pos_from_cursor(&cursor)
+        .translate(output.text_draw_pos.to_vec2());
+    ui.scroll_to_rect(rect, Some(egui::Align::Center));
+    output
+        .state
+        .set_ccursor_range(Some(CCursorRange::two(start, end)));
+    output.state.store(ui.ctx(), output.response.id);
+    Some(range)
+}
+
+/// Number each line of the editor's `galley` in the gutter left of `editor_rect`. Rows come
+/// from the laid-out text, so the numbers follow edits and stay level with their lines.
+fn paint_line_numbers(ui: &Ui, galley: &Galley, text_pos: egui::Pos2, editor_rect: &egui::Rect) {
+    let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+    let color = ui.visuals().weak_text_color();
+    let mut line = 1;
+    let mut starts_line = true;
+    for row in &galley.rows {
+        if starts_line {
+            ui.painter().text(
+                egui::pos2(editor_rect.left() - 4.0, text_pos.y + row.rect.top()),
+                egui::Align2::RIGHT_TOP,
+                line.to_string(),
+                font_id.clone(),
+                color,
+            );
+            line += 1;
+        }
+        starts_line = row.ends_with_newline;
+    }
+}
+
+/// Minimal syntax highlighter:
+/// - Lines starting with '#' -> gray comment
+/// - Everything else -> green (shade depends on the theme)
+///
+/// Find matches (byte ranges of `code`, in order) get a yellow background. Ranges that no
+/// longer fit the text, as in the frame the text is edited, are ignored.
+fn highlight_config_text(
+    ui: &egui::Ui,
+    code: &str,
+    theme: Theme,
+    flagged: &[usize],
+    matches: &[Range<usize>],
+) -> Arc<Galley> {
+    let palette = config_palette(theme);
+    let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+    let mut job = LayoutJob::default();
+    let mut matches = matches
+        .iter()
+        .filter(|m| {
+            m.end <= code.len() && code.is_char_boundary(m.start) && code.is_char_boundary(m.end)
+        })
+        .peekable();
+    let mut offset = 0;
+
+    for (line, chunk) in code.split_inclusive('\n').enumerate() {
+        let is_comment = chunk.trim_start().starts_with('#');
+        let color = if is_comment {
+            palette.comment
+        } else {
+            palette.value
+        };
+
+        let background = if flagged.binary_search(&line).is_ok() {
+            Color32::from_rgba_unmultiplied(255, 165, 0, 60)
+        } else {
+            Color32::TRANSPARENT
+        };
+
+        let end = offset + chunk.len();
+        while offset < end {
+            while matches.next_if(|m| m.end <= offset).is_some() {}
+            let (next, background) = match matches.peek() {
+                Some(m) if m.start <= offset => (
+                    m.end.min(end),
+                    Color32::from_rgba_unmultiplied(255, 255, 0, 80),
+                ),
+                Some(m) => (m.start.min(end), background),
+                None => (end, background),
+            };
+            let format = TextFormat {
+                font_id: font_id.clone(),
+                color,
+                background,
+                ..Default::default()
+            };
+            job.append(&code[offset..next], 0.0, format);
+            offset = next;
+        }
+    }
+
+    ui.fonts(|fonts| fonts.layout_job(job))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    const FRAMES: u32 = 30;
+
+    /// Average time to build a Terminal tab frame with `lines` lines of log, as the ms/frame
+    /// readout shows it. Not a regular test; run it with
+    /// `cargo test --release log_view_frame_times -- --ignored --nocapture`.
+    fn frame_time_ms(lines: usize, lightweight: bool) -> f64 {
+        let mut app = ThetaApp::new();
+        app.headless = false;
+        app.max_log_lines = lines;
+        app.lightweight_log_view = lightweight;
+        app.log_buffer.clear();
+        for n in 0..lines {
+            let line = format!("[INFO] \x1b[32mMDDS\x1b[0m quote {n}: bid 101.25 ask 101.30\n");
+            app.log_buffer.push_str(&line, lines);
+        }
+        let ctx = egui::Context::default();
+        let input = || egui::RawInput {
+            screen_rect: Some(egui::Rect::from_min_size(
+                egui::Pos2::ZERO,
+                egui::vec2(1280.0, 800.0),
+            )),
+            ..Default::default()
+        };
+        let mut frame = || {
+            ctx.run(input(), |ctx| {
+                egui::CentralPanel::default().show(ctx, |ui| show_terminal_tab(&mut app, ui));
+            })
+        };
+        // The first frame loads fonts and fills the caches.
+        frame();
+        let started = Instant::now();
+        for _ in 0..FRAMES {
+            frame();
+        }
+        started.elapsed().as_secs_f64() * 1000.0 / f64::from(FRAMES)
+    }
+
+    #[test]
+    #[ignore = "benchmark"]
+    fn log_view_frame_times() {
+        println!("{:>8}  {:>10}  {:>12}", "lines", "full view", "lightweight");
+        for lines in [1_000, 10_000, 50_000, 100_000] {
+            println!(
+                "{lines:>8}  {:>7.2} ms  {:>9.2} ms",
+                frame_time_ms(lines, false),
+                frame_time_ms(lines, true)
+            );
+        }
+    }
 }
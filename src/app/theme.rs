@@ -0,0 +1,73 @@
+use crate::model::Theme;
+use eframe::egui::{Color32, Stroke, Visuals};
+
+/// Orange for warnings and in-between states, shared so they all match.
+pub const WARNING_ORANGE: Color32 = Color32::from_rgb(255, 165, 0);
+
+/// Colors used by the config highlighter.
+pub struct ConfigPalette {
+    pub comment: Color32,
+    pub value: Color32,
+}
+
+/// egui visuals for the selected theme.
+pub fn theme_visuals(theme: Theme) -> Visuals {
+    match theme {
+        Theme::Dark => Visuals::dark(),
+        Theme::Light => Visuals::light(),
+        Theme::HighContrast => high_contrast_visuals(),
+    }
+}
+
+/// Highlighter colors that stay readable on the theme's editor background.
+pub fn config_palette(theme: Theme) -> ConfigPalette {
+    match theme {
+        Theme::Dark => ConfigPalette {
+            comment: Color32::LIGHT_GRAY,
+            value: Color32::from_rgb(150, 255, 150),
+        },
+        Theme::Light => ConfigPalette {
+            comment: Color32::from_gray(90),
+            value: Color32::from_rgb(0, 100, 0),
+        },
+        // Both well above the WCAG AAA 7:1 ratio on black.
+        Theme::HighContrast => ConfigPalette {
+            comment: Color32::from_gray(200),
+            value: Color32::from_rgb(0, 255, 0),
+        },
+    }
+}
+
+/// White-on-black with thick yellow outlines on hovered, active and focused widgets.
+fn high_contrast_visuals() -> Visuals {
+    let mut visuals = Visuals::dark();
+    let focus = Stroke::new(2.0, Color32::YELLOW);
+
+    visuals.override_text_color = Some(Color32::WHITE);
+    visuals.panel_fill = Color32::BLACK;
+    visuals.window_fill = Color32::BLACK;
+    visuals.extreme_bg_color = Color32::BLACK;
+    visuals.faint_bg_color = Color32::from_gray(24);
+    visuals.hyperlink_color = Color32::from_rgb(0, 255, 255);
+    visuals.selection.bg_fill = Color32::from_rgb(0, 70, 160);
+    visuals.selection.stroke = focus;
+    visuals.window_stroke = Stroke::new(2.0, Color32::WHITE);
+
+    for widget in [
+        &mut visuals.widgets.noninteractive,
+        &mut visuals.widgets.inactive,
+        &mut visuals.widgets.open,
+    ] {
+        widget.bg_fill = Color32::BLACK;
+        widget.weak_bg_fill = Color32::BLACK;
+        widget.bg_stroke = Stroke::new(1.0, Color32::WHITE);
+        widget.fg_stroke = Stroke::new(1.5, Color32::WHITE);
+    }
+    for widget in [&mut visuals.widgets.hovered, &mut visuals.widgets.active] {
+        widget.bg_fill = Color32::from_gray(40);
+        widget.weak_bg_fill = Color32::from_gray(40);
+        widget.bg_stroke = focus;
+        widget.fg_stroke = Stroke::new(2.0, Color32::WHITE);
+    }
+    visuals
+}
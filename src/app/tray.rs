@@ -0,0 +1,161 @@
+//! System tray icon, so the window can be hidden while the terminal keeps running. Only
+//! available on Windows and macOS; a Linux tray needs a GTK main loop this app doesn't run.
+//!
+//! A hidden window gets no input events, so nothing would call `update` to poll the menu.
+//! Menu picks are therefore forwarded from a background thread that also requests a
+//! repaint, which wakes the event loop.
+
+/// What the user picked from the tray menu.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrayAction {
+    Show,
+    ToggleTerminal,
+    Quit,
+}
+
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+pub use self::native::Tray;
+
+/// Get the window out of the way while the terminal keeps running. On Windows the window is
+/// hidden and `update` keeps running. On macOS a hidden window stops being redrawn, which
+/// would stall the UI loop, so it is minimized to the Dock instead.
+pub fn hide_window(frame: &mut eframe::Frame) {
+    if cfg!(target_os = "macos") {
+        frame.set_minimized(true);
+    } else {
+        frame.set_visible(false);
+    }
+}
+
+/// Undo `hide_window`.
+pub fn show_window(frame: &mut eframe::Frame) {
+    if cfg!(target_os = "macos") {
+        frame.set_minimized(false);
+    }
+    frame.set_visible(true);
+}
+
+#[cfg(any(target_os = "windows", target_os = "macos"))]
+mod native {
+    use super::TrayAction;
+    use eframe::egui;
+    use std::sync::mpsc::{self, Receiver, Sender};
+    use std::sync::{Mutex, OnceLock};
+    use tray_icon::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+    use tray_icon::{Icon, TrayIcon, TrayIconBuilder};
+
+    /// Where the forwarding thread delivers menu events: the channel of the current tray and
+    /// the context to wake. Replaced when the tray is recreated, so only one thread ever
+    /// reads tray-icon's global receiver.
+    static FORWARD: OnceLock<Mutex<Option<(Sender<MenuEvent>, egui::Context)>>> = OnceLock::new();
+
+    fn forward_menu_events(events: Sender<MenuEvent>, ctx: egui::Context) {
+        let mut started = false;
+        let slot = FORWARD.get_or_init(|| {
+            started = true;
+            Mutex::new(None)
+        });
+        *slot.lock().unwrap_or_else(|e| e.into_inner()) = Some((events, ctx));
+        if !started {
+            return;
+        }
+        std::thread::spawn(move || {
+            while let Ok(event) = MenuEvent::receiver().recv() {
+                let target = slot.lock().unwrap_or_else(|e| e.into_inner());
+                if let Some((events, ctx)) = target.as_ref() {
+                    if events.send(event).is_ok() {
+                        ctx.request_repaint();
+                    }
+                }
+            }
+        });
+    }
+
+    pub struct Tray {
+        icon: TrayIcon,
+        show: MenuItem,
+        toggle: MenuItem,
+        quit: MenuItem,
+        events: Receiver<MenuEvent>,
+    }
+
+    impl Tray {
+        pub fn new(ctx: &egui::Context) -> Result<Self, String> {
+            let show = MenuItem::new("Show", true, None);
+            let toggle = MenuItem::new("Start Terminal", true, None);
+            let quit = MenuItem::new("Quit", true, None);
+            let menu = Menu::new();
+            menu.append_items(&[&show, &toggle, &PredefinedMenuItem::separator(), &quit])
+                .map_err(|e| e.to_string())?;
+            let icon = TrayIconBuilder::new()
+                .with_menu(Box::new(menu))
+                .with_tooltip("ThetaData Terminal GUI")
+                .with_icon(app_icon()?)
+                .build()
+                .map_err(|e| e.to_string())?;
+            let (sender, events) = mpsc::channel();
+            forward_menu_events(sender, ctx.clone());
+            Ok(Self {
+                icon,
+                show,
+                toggle,
+                quit,
+                events,
+            })
+        }
+
+        /// Show `status` as the tooltip and label the toggle item for the current state.
+        pub fn update(&self, status: &str, running: bool) {
+            let _ = self
+                .icon
+                .set_tooltip(Some(format!("ThetaData Terminal GUI — {status}")));
+            self.toggle.set_text(if running {
+                "Stop Terminal"
+            } else {
+                "Start Terminal"
+            });
+        }
+
+        /// The next menu pick since the last call, if any.
+        pub fn poll(&self) -> Option<TrayAction> {
+            while let Ok(event) = self.events.try_recv() {
+                if event.id == self.show.id() {
+                    return Some(TrayAction::Show);
+                } else if event.id == self.toggle.id() {
+                    return Some(TrayAction::ToggleTerminal);
+                } else if event.id == self.quit.id() {
+                    return Some(TrayAction::Quit);
+                }
+            }
+            None
+        }
+    }
+
+    fn app_icon() -> Result<Icon, String> {
+        #[cfg(target_os = "windows")]
+        let bytes = include_bytes!("../../resources/Win_App_Icon.png");
+        #[cfg(target_os = "macos")]
+        let bytes = include_bytes!("../../resources/Mac_App_Icon.png");
+        let image = image::load_from_memory(bytes)
+            .map_err(|e| e.to_string())?
+            .to_rgba8();
+        let (width, height) = image.dimensions();
+        Icon::from_rgba(image.into_raw(), width, height).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub struct Tray;
+
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+impl Tray {
+    pub fn new(_ctx: &eframe::egui::Context) -> Result<Self, String> {
+        Err("the system tray isn't supported on this platform".to_string())
+    }
+
+    pub fn update(&self, _status: &str, _running: bool) {}
+
+    pub fn poll(&self) -> Option<TrayAction> {
+        None
+    }
+}
@@ -0,0 +1,59 @@
+//! Opt-in check for a newer release of this app.
+
+use serde::Deserialize;
+use std::time::Duration;
+
+/// Latest published release of this GUI.
+const RELEASES_URL: &str =
+    "https://api.github.com/repos/veppy1/thetadata_terminal_gui/releases/latest";
+
+/// Minimum time between checks, in seconds.
+pub const CHECK_INTERVAL_SECS: i64 = 24 * 60 * 60;
+
+pub struct Release {
+    pub version: String,
+    pub notes_url: String,
+}
+
+#[derive(Deserialize)]
+struct ReleaseMetadata {
+    tag_name: String,
+    html_url: String,
+}
+
+/// Fetch the latest release. Blocking; call from a background thread.
+pub fn fetch_latest() -> Result<Release, String> {
+    let body = ureq::get(RELEASES_URL)
+        .set(
+            "User-Agent",
+            concat!("thetadata_terminal_gui/", env!("CARGO_PKG_VERSION")),
+        )
+        .timeout(Duration::from_secs(10))
+        .call()
+        .map_err(|e| e.to_string())?
+        .into_string()
+        .map_err(|e| e.to_string())?;
+    let metadata: ReleaseMetadata = serde_json::from_str(&body).map_err(|e| e.to_string())?;
+    Ok(Release {
+        version: metadata.tag_name.trim_start_matches('v').to_string(),
+        notes_url: metadata.html_url,
+    })
+}
+
+/// Whether `candidate` is a later `major.minor.patch` than `current`. Missing parts count
+/// as 0 and anything after `-` or `+` is ignored.
+pub fn is_newer(candidate: &str, current: &str) -> bool {
+    match (parse_version(candidate), parse_version(current)) {
+        (Some(candidate), Some(current)) => candidate > current,
+        _ => false,
+    }
+}
+
+fn parse_version(version: &str) -> Option<(u64, u64, u64)> {
+    let core = version.trim_start_matches('v').split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|p| p.parse::<u64>());
+    let major = parts.next()?.ok()?;
+    let minor = parts.next().unwrap_or(Ok(0)).ok()?;
+    let patch = parts.next().unwrap_or(Ok(0)).ok()?;
+    Some((major, minor, patch))
+}
@@ -0,0 +1,16 @@
+//! Small helpers shared by modules that run external programs.
+
+use std::process::Command;
+
+/// A command that won't flash a console window on Windows.
+pub fn hidden_command(program: &str) -> Command {
+    #[allow(unused_mut)]
+    let mut command = Command::new(program);
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+    command
+}
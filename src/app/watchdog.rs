@@ -0,0 +1,75 @@
+//! `--watchdog`: supervise the terminal without a window. Starts it, restarts it when it
+//! exits (backing off if it keeps dying), and logs to stdout and the log file until
+//! interrupted.
+
+use super::ThetaApp;
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+const TICK: Duration = Duration::from_millis(100);
+const MIN_RESTART_DELAY: Duration = Duration::from_secs(5);
+const MAX_RESTART_DELAY: Duration = Duration::from_secs(300);
+/// A run at least this long counts as healthy and resets the restart backoff.
+pub const STABLE_RUN: Duration = Duration::from_secs(60);
+
+pub fn run() {
+    let mut app = ThetaApp::new();
+    app.headless = true;
+    // Anything logged while loading (warnings) went to the screen buffer.
+    print!("{}", app.log_buffer.take());
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let stop_requested = Arc::clone(&stop);
+    if let Err(e) = ctrlc::set_handler(move || stop_requested.store(true, Ordering::Relaxed)) {
+        app.append_log(&format!("Could not install the Ctrl+C handler: {e}\n"));
+    }
+    app.append_log("Watchdog started; press Ctrl+C to stop.\n");
+
+    let mut failures = 0; // short runs or failed starts in a row
+    let mut restart_at = Some(Instant::now());
+    let mut started: Option<Instant> = None;
+    while !stop.load(Ordering::Relaxed) {
+        if app.supervise().is_some() {
+            let ran_for = started.take().map_or(Duration::ZERO, |s| s.elapsed());
+            failures = if ran_for >= STABLE_RUN {
+                0
+            } else {
+                failures + 1
+            };
+            let delay = restart_delay(failures);
+            app.append_log(&format!("Restarting in {}s.\n", delay.as_secs()));
+            restart_at = Some(Instant::now() + delay);
+        }
+
+        if app.process.is_none() && restart_at.is_some_and(|at| Instant::now() >= at) {
+            app.start_terminal();
+            if app.process.is_some() {
+                started = Some(Instant::now());
+                restart_at = None;
+            } else {
+                // start_terminal already logged why (missing jar, credentials, …).
+                failures += 1;
+                let delay = restart_delay(failures);
+                app.append_log(&format!("Retrying in {}s.\n", delay.as_secs()));
+                restart_at = Some(Instant::now() + delay);
+            }
+        }
+        thread::sleep(TICK);
+    }
+
+    app.append_log("Watchdog stopping.\n");
+    app.force_quit_process();
+}
+
+/// Doubles with each consecutive failure, starting from `MIN_RESTART_DELAY`.
+pub fn restart_delay(failures: u32) -> Duration {
+    MIN_RESTART_DELAY
+        .saturating_mul(1 << failures.saturating_sub(1).min(16))
+        .min(MAX_RESTART_DELAY)
+}
@@ -4,9 +4,49 @@ mod app;
 mod model;
 
 use crate::app::ThetaApp;
-use eframe::egui::Vec2;
+use crate::model::AppConfig;
+use eframe::egui::{Pos2, Vec2};
+
+/// `--watchdog` and `--start-detached` print status and the watchdog stops on Ctrl+C, but a
+/// `windows_subsystem = "windows"` binary starts without a console. Attach to the console of
+/// the shell that launched us, if any, so output shows up there and Ctrl+C reaches us.
+/// The shell doesn't wait for a GUI-subsystem process, so its prompt comes back right away.
+#[cfg(target_os = "windows")]
+fn attach_parent_console() {
+    use windows_sys::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+    // Fails when started from Explorer or a scheduler, which have no console; nothing to do then.
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn attach_parent_console() {}
 
 fn main() {
+    // Headless supervisor: keep the terminal alive without opening a window.
+    if std::env::args().any(|arg| arg == "--watchdog") {
+        attach_parent_console();
+        app::watchdog::run();
+        return;
+    }
+
+    // Helper spawned by the GUI: stop the terminal once the GUI is gone.
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(pid) = args
+        .iter()
+        .position(|arg| arg == "--guard")
+        .and_then(|i| args.get(i + 1)?.parse().ok())
+    {
+        std::process::exit(app::guard::run(pid));
+    }
+
+    // Fire-and-forget: start the terminal detached, report readiness and exit.
+    if std::env::args().any(|arg| arg == "--start-detached") {
+        attach_parent_console();
+        std::process::exit(app::detached::run());
+    }
+
     // Optional: load an icon
     let icon_bytes = {
         #[cfg(target_os = "windows")]
@@ -31,12 +71,21 @@ fn main() {
         height,
     };
 
+    // Window settings are needed before the app starts; it loads the rest of the config itself.
+    let cfg: AppConfig = confy::load("thetadata_terminal_manager", None).unwrap_or_default();
+    let (min_width, min_height) = cfg.min_window_size();
+    let (width, height) = cfg.window.map_or((300.0, 300.0), |w| w.size);
+
     // Configure eframe
     let native_options = eframe::NativeOptions {
-        // 1) Start at 300×300
-        initial_window_size: Some(Vec2::new(300.0, 300.0)),
-        // 2) Minimum window size also 300×300
-        min_window_size: Some(Vec2::new(300.0, 300.0)),
+        // 1) Start where the window was last time, or at 300×300; never below the minimum
+        initial_window_size: Some(Vec2::new(min_width.max(width), min_height.max(height))),
+        initial_window_pos: cfg
+            .window
+            .and_then(|w| w.position)
+            .map(|(x, y)| Pos2::new(x, y)),
+        // 2) Minimum window size from the config
+        min_window_size: Some(Vec2::new(min_width, min_height)),
         // Allow resizing
         resizable: true,
         // Set icon
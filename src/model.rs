@@ -1,12 +1,278 @@
 use serde::{Deserialize, Serialize};
 
+/// Port ThetaTerminal's REST API listens on unless its config says otherwise.
+pub const DEFAULT_REST_PORT: u16 = 25510;
+
+/// Status paths tried in order when none are configured: v2 terminals first, then v3.
+pub const DEFAULT_STATUS_PATHS: [&str; 2] = ["/v2/system/mdds/status", "/v3/system/mdds/status"];
+
+/// Feed statistics path tried when none is configured.
+pub const DEFAULT_STATS_PATH: &str = "/v2/system/stats";
+
+/// Port the log mirror listens on unless configured otherwise.
+pub const DEFAULT_MIRROR_PORT: u16 = 25599;
+
+/// Log timestamp pattern used when none is set or the configured one is invalid.
+pub const DEFAULT_TIMESTAMP_FORMAT: &str = "%H:%M:%S%.3f";
+
+/// Smallest minimum window size the settings accept, enough for the compact layout.
+pub const MIN_WINDOW_FLOOR: (f32, f32) = (150.0, 100.0);
+
+/// Smallest and largest monospace font size the settings accept.
+pub const FONT_SIZE_RANGE: (f32, f32) = (8.0, 32.0);
+
 /// Stored app configuration, loaded/saved with confy.
-#[derive(Serialize, Deserialize, Default)]
+///
+/// Missing fields fall back to their defaults so older config files keep loading.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
 pub struct AppConfig {
     pub jar_path: Option<String>,
+    /// Java executable to launch the jar with; `None` uses `java`/`javaw` from PATH.
+    pub java_path: Option<String>,
     pub auto_start: bool,
+    /// Start the terminal again when it exits with a failure (not after Stop), with backoff.
+    pub auto_restart: bool,
+    /// How long Stop waits for the terminal to exit after asking it to, before killing it.
+    pub shutdown_grace_ms: u64,
+    /// What happens to the terminal if this app dies without shutting down cleanly.
+    pub on_gui_crash: GuiCrashBehavior,
+    /// Keep the credentials in memory after the first keyring read this session, so later
+    /// starts don't prompt again. Off by default.
+    pub cache_credentials_in_memory: bool,
+    /// Named credential profiles besides the default one, each with its own keyring entries.
+    pub profiles: Vec<String>,
+    /// The selected profile; empty for the default.
+    pub profile: String,
     pub default_tab: Tab,
     pub thetadata_config_path: Option<String>,
+    /// Redirect terminal stdout/stderr to a file and tail it instead of piping every line.
+    pub capture_output_to_file: bool,
+    /// JVM flag preset used when starting the terminal.
+    pub jvm_profile: JvmProfile,
+    /// Free-form JVM arguments for the Custom profile.
+    pub jvm_args: String,
+    /// Pass the OS timezone to the JVM as `-Duser.timezone`, unless the JVM arguments
+    /// already set one.
+    pub pass_system_timezone: bool,
+    /// Load the config the terminal reports using as soon as it is detected.
+    pub auto_load_detected_config: bool,
+    /// Wait this long after detection before auto-loading, while the terminal may still be
+    /// writing the file.
+    pub auto_load_delay_ms: u64,
+    /// Keep the config Save bar visible on every tab while there are unsaved changes.
+    pub save_panel_on_all_tabs: bool,
+    /// Show only the file name of the jar and config paths; the full path is on hover.
+    pub show_path_basenames: bool,
+    /// Ask to save unsaved config edits when Start or Reset is clicked.
+    pub confirm_start_with_unsaved_config: bool,
+    /// Ask before closing the window while the terminal is running.
+    pub confirm_quit_while_running: bool,
+    /// Closing the window while the terminal runs hides it to the system tray instead of
+    /// quitting; Quit in the tray menu exits. Windows and macOS only.
+    pub minimize_to_tray: bool,
+    /// Editor used by "Open in external editor"; empty uses the OS default for the file.
+    pub external_editor_command: String,
+    /// Free-form notes (account quirks, expiry dates, …) shown in App Configuration.
+    pub notes: String,
+    /// Look for a newer release of this app once a day. Off by default; no network
+    /// request is made while it is off.
+    pub check_for_updates: bool,
+    /// Unix time of the last update check.
+    pub last_update_check: Option<i64>,
+    /// Newer release found by the last check, kept so the banner survives restarts.
+    pub available_update: Option<String>,
+    pub available_update_url: Option<String>,
+    /// Copy the config file to a timestamped backup before every terminal start.
+    pub backup_config_on_start: bool,
+    /// Copy the config file to a timestamped backup before every save overwrites it.
+    pub keep_backups: bool,
+    /// How many backups of a config file to keep; older ones are deleted.
+    pub backup_retention: usize,
+    /// Recently opened config files, most recent first.
+    pub recent_config_paths: Vec<String>,
+    /// How the Config tab shows the loaded config.
+    pub config_view_mode: ConfigViewMode,
+    /// Show a line-number gutter beside the config editor.
+    pub config_line_numbers: bool,
+    /// How the validator treats keys set more than once in the config.
+    pub duplicate_key_policy: DuplicateKeyPolicy,
+
+    // Terminal view preferences. Only the long-lived toggles are persisted; anything
+    // tied to the current log contents (selection, scroll offset) resets every launch.
+    pub follow_output: bool,
+    pub wrap_log_lines: bool,
+    /// Lay out only the visible log lines, so frame time doesn't grow with the buffer.
+    pub lightweight_log_view: bool,
+    pub show_timestamps: bool,
+    /// chrono strftime pattern for log timestamps, e.g. `%Y-%m-%dT%H:%M:%S%.3f%:z`.
+    pub log_timestamp_format: String,
+    /// Stamp lines in UTC instead of local time.
+    pub log_timestamp_utc: bool,
+    /// Hold output briefly and merge stdout/stderr by receive time instead of showing
+    /// each line the moment its reader thread delivers it.
+    pub order_output_by_time: bool,
+    /// Lines kept in the on-screen log; older ones are dropped (the log file keeps them).
+    pub max_log_lines: usize,
+    /// Write a marker line to the log when Start/Stop/Reset/Save are clicked.
+    pub mark_user_actions: bool,
+    /// Remove ANSI color codes from logs written with Save Log….
+    pub strip_ansi_in_saved_log: bool,
+    /// Lines per second above which the Terminal tab only shows a sample of the output.
+    pub flood_threshold: usize,
+
+    /// Play a sound when an error or auth failure is logged or the connection drops.
+    pub alert_sound_enabled: bool,
+    /// 0.0–1.0; honoured where the platform's player supports it.
+    pub alert_volume: f32,
+    /// Show a desktop notification when the terminal exits without Stop being clicked.
+    pub notify_on_exit: bool,
+
+    /// Port of the launched terminal's REST API; must match `HTTP_PORT` in its config.
+    pub rest_port: u16,
+    /// Status endpoint paths to try, in order; the first one that answers is used.
+    pub status_paths: Vec<String>,
+    /// Feed statistics endpoint path; empty turns statistics off.
+    pub stats_path: String,
+    /// Terminal to watch in monitoring-only mode (no credentials saved); may be remote.
+    pub monitor_host: String,
+    pub monitor_port: u16,
+
+    pub theme: Theme,
+    /// Size of the monospace font used by the terminal log and the config editor.
+    pub font_size: f32,
+    /// Smallest size the window can be resized to; read at startup.
+    pub min_window_width: f32,
+    pub min_window_height: f32,
+
+    /// Also write the log to one file per day in the app config directory.
+    pub log_to_file: bool,
+    /// Hard-wrap long lines in the log file (the on-screen log is unaffected).
+    pub log_file_wrap: bool,
+    pub log_file_wrap_width: usize,
+
+    /// Stream new log lines to TCP clients for remote tailing.
+    pub log_mirror_enabled: bool,
+    pub log_mirror_port: u16,
+    /// Listen on every interface instead of localhost only.
+    pub log_mirror_all_interfaces: bool,
+
+    // Table-valued fields (structs and lists of structs) must come last:
+    // TOML cannot emit plain values after a table.
+    /// Window size and position when the app last ran; `None` lets the OS place it.
+    pub window: Option<WindowGeometry>,
+    /// Minimum Java version the configured jar reported needing on a previous launch.
+    pub java_requirement: Option<JavaRequirement>,
+    /// Named stdin command sequences shown as buttons on the Terminal tab.
+    pub macros: Vec<CommandMacro>,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            jar_path: None,
+            java_path: None,
+            auto_start: false,
+            auto_restart: false,
+            shutdown_grace_ms: 3000,
+            on_gui_crash: GuiCrashBehavior::default(),
+            cache_credentials_in_memory: false,
+            profiles: Vec::new(),
+            profile: String::new(),
+            default_tab: Tab::default(),
+            thetadata_config_path: None,
+            capture_output_to_file: false,
+            jvm_profile: JvmProfile::default(),
+            jvm_args: String::new(),
+            pass_system_timezone: false,
+            auto_load_detected_config: false,
+            auto_load_delay_ms: 1000,
+            save_panel_on_all_tabs: true,
+            show_path_basenames: false,
+            confirm_start_with_unsaved_config: true,
+            confirm_quit_while_running: true,
+            minimize_to_tray: false,
+            external_editor_command: String::new(),
+            backup_config_on_start: false,
+            keep_backups: true,
+            backup_retention: 10,
+            notes: String::new(),
+            check_for_updates: false,
+            last_update_check: None,
+            available_update: None,
+            available_update_url: None,
+            recent_config_paths: Vec::new(),
+            config_view_mode: ConfigViewMode::default(),
+            config_line_numbers: true,
+            duplicate_key_policy: DuplicateKeyPolicy::default(),
+            follow_output: true,
+            wrap_log_lines: true,
+            lightweight_log_view: false,
+            show_timestamps: false,
+            log_timestamp_format: DEFAULT_TIMESTAMP_FORMAT.to_string(),
+            log_timestamp_utc: false,
+            order_output_by_time: false,
+            max_log_lines: 10_000,
+            mark_user_actions: true,
+            strip_ansi_in_saved_log: true,
+            flood_threshold: 5000,
+            alert_sound_enabled: false,
+            alert_volume: 0.7,
+            notify_on_exit: false,
+            rest_port: DEFAULT_REST_PORT,
+            status_paths: DEFAULT_STATUS_PATHS.map(String::from).to_vec(),
+            stats_path: DEFAULT_STATS_PATH.to_string(),
+            monitor_host: "127.0.0.1".to_string(),
+            monitor_port: DEFAULT_REST_PORT,
+            theme: Theme::default(),
+            font_size: 14.0,
+            min_window_width: 300.0,
+            min_window_height: 300.0,
+            log_to_file: false,
+            log_file_wrap: false,
+            log_file_wrap_width: 160,
+            log_mirror_enabled: false,
+            log_mirror_port: DEFAULT_MIRROR_PORT,
+            log_mirror_all_interfaces: false,
+            window: None,
+            java_requirement: None,
+            macros: Vec::new(),
+        }
+    }
+}
+
+impl AppConfig {
+    /// The configured minimum window size, raised to `MIN_WINDOW_FLOOR` if set lower.
+    pub fn min_window_size(&self) -> (f32, f32) {
+        (
+            self.min_window_width.max(MIN_WINDOW_FLOOR.0),
+            self.min_window_height.max(MIN_WINDOW_FLOOR.1),
+        )
+    }
+}
+
+/// Window geometry in points, as eframe reports it.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct WindowGeometry {
+    pub size: (f32, f32),
+    /// `None` where the platform doesn't report window positions.
+    pub position: Option<(f32, f32)>,
+    /// Size of the monitor the window was on, to notice when that monitor is gone.
+    pub monitor_size: Option<(f32, f32)>,
+}
+
+/// A Java version requirement learned from an `UnsupportedClassVersionError`.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct JavaRequirement {
+    pub jar_path: String,
+    pub major: u32,
+}
+
+/// A named sequence of stdin commands, one per line.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct CommandMacro {
+    pub name: String,
+    pub commands: String,
 }
 
 /// Which tab is selected
@@ -22,3 +288,110 @@ impl Default for Tab {
         Self::Setup
     }
 }
+
+/// Color theme for the whole UI, including the config highlighter and terminal output.
+#[derive(PartialEq, Serialize, Deserialize, Clone, Copy)]
+pub enum Theme {
+    Dark,
+    Light,
+    HighContrast,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::Dark
+    }
+}
+
+/// How the Config tab presents the config file. All modes show the same text, so
+/// switching never loses unsaved edits.
+#[derive(PartialEq, Serialize, Deserialize, Clone, Copy)]
+pub enum ConfigViewMode {
+    Text,
+    Structured,
+    ViewOnly,
+}
+
+impl ConfigViewMode {
+    pub const ALL: [Self; 3] = [Self::Text, Self::Structured, Self::ViewOnly];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Text => "Text",
+            Self::Structured => "Structured",
+            Self::ViewOnly => "View only",
+        }
+    }
+
+    /// The mode after this one, wrapping around; used by the cycle shortcut.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Text => Self::Structured,
+            Self::Structured => Self::ViewOnly,
+            Self::ViewOnly => Self::Text,
+        }
+    }
+}
+
+impl Default for ConfigViewMode {
+    fn default() -> Self {
+        Self::Text
+    }
+}
+
+/// What to do about a key set on more than one line. ThetaTerminal doesn't document
+/// which occurrence wins, so by default all of them are only highlighted.
+#[derive(PartialEq, Serialize, Deserialize, Clone, Copy)]
+pub enum DuplicateKeyPolicy {
+    Warn,
+    KeepFirst,
+    KeepLast,
+}
+
+impl DuplicateKeyPolicy {
+    pub const ALL: [Self; 3] = [Self::Warn, Self::KeepFirst, Self::KeepLast];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Warn => "Warn",
+            Self::KeepFirst => "Keep first",
+            Self::KeepLast => "Keep last",
+        }
+    }
+}
+
+impl Default for DuplicateKeyPolicy {
+    fn default() -> Self {
+        Self::Warn
+    }
+}
+
+/// The terminal's fate when the GUI crashes or is killed (a normal exit always stops it).
+#[derive(PartialEq, Serialize, Deserialize, Clone, Copy)]
+pub enum GuiCrashBehavior {
+    /// Started in its own process group so it keeps running on its own.
+    LeaveRunning,
+    /// A `--guard` helper stops it as soon as the GUI is gone.
+    StopTerminal,
+}
+
+impl Default for GuiCrashBehavior {
+    fn default() -> Self {
+        Self::LeaveRunning
+    }
+}
+
+/// Named set of JVM flags passed before `-jar` when starting the terminal.
+#[derive(PartialEq, Serialize, Deserialize, Clone, Copy)]
+pub enum JvmProfile {
+    Default,
+    LowLatency,
+    LowMemory,
+    Custom,
+}
+
+impl Default for JvmProfile {
+    fn default() -> Self {
+        Self::Default
+    }
+}